@@ -140,11 +140,7 @@ async fn main() {
         }
     };
 
-    let next_expiration = match chain.expirations.iter().min_by(|a, b| {
-        a.days_to_expiration
-            .cmp(&b.days_to_expiration)
-            .then_with(|| a.expiration_date.cmp(&b.expiration_date))
-    }) {
+    let next_expiration = match chain.nearest_expiration(0) {
         Some(expiration) => expiration,
         None => {
             eprintln!("No expirations found for {symbol}.");
@@ -199,13 +195,18 @@ async fn main() {
         request.add_equity_option(strike.put.0.clone());
     }
 
-    let market_data = match tasty.fetch_market_data(&request).await {
-        Ok(data) => data,
-        Err(e) => {
-            eprintln!("Failed to fetch market data: {e}");
-            process::exit(1);
-        }
-    };
+    let report = tasty.fetch_market_data_report(&request).await;
+    for failure in &report.failures {
+        eprintln!(
+            "Failed to fetch market data for {}: {}",
+            failure.symbols.join(", "),
+            failure.error
+        );
+    }
+    if report.items.is_empty() && !report.is_success() {
+        process::exit(1);
+    }
+    let market_data = report.items;
 
     let mut by_symbol: HashMap<String, MarketDataItem> = HashMap::new();
     for item in market_data.into_iter().chain(equity_only.into_iter()) {
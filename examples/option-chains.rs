@@ -2,7 +2,9 @@ use rust_decimal::prelude::*;
 use std::collections::HashMap;
 use std::process;
 use std::time::Duration;
+use tastytrade_rs::api::chain_query::ExpirationFilter;
 use tastytrade_rs::api::oauth2::OAuth2Config;
+use tastytrade_rs::api::option_chain::{Expiration, Strike};
 use tastytrade_rs::api::quote_streaming::{GreeksData, QuoteData, StreamerEventData};
 use tastytrade_rs::TastyTrade;
 
@@ -32,6 +34,13 @@ fn get_oauth_config() -> (OAuth2Config, String) {
     (config, refresh_token)
 }
 
+fn strikes_in_range(expiration: &Expiration, range: Option<(Decimal, Decimal)>) -> Vec<&Strike> {
+    match range {
+        Some((min, max)) => expiration.strikes_between(min, max),
+        None => expiration.strikes.iter().collect(),
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let args: Vec<String> = std::env::args().skip(1).collect();
@@ -143,28 +152,21 @@ async fn main() {
         }
     };
 
+    let mut filter = ExpirationFilter::new();
+    if let Some(max_dte) = max_dte {
+        filter = filter.max_dte(max_dte);
+    }
+    let expirations = chain.filter_expirations(&filter);
+    let strike_range = strike_min.zip(strike_max);
+
     // Collect streaming data FIRST if requested
     if with_streaming {
         println!("\n=== Fetching Live Market Data ===");
 
         // Collect all option symbols that pass our filters and get their streamer symbols
         let mut regular_symbols = Vec::new();
-        for expiration in &chain.expirations {
-            // Apply DTE filter
-            if let Some(max_dte) = max_dte {
-                if expiration.days_to_expiration > max_dte {
-                    continue;
-                }
-            }
-
-            for strike in &expiration.strikes {
-                // Apply strike range filter
-                if let (Some(min), Some(max)) = (strike_min, strike_max) {
-                    if strike.strike_price < min || strike.strike_price > max {
-                        continue;
-                    }
-                }
-
+        for expiration in &expirations {
+            for strike in strikes_in_range(expiration, strike_range) {
                 regular_symbols.push(strike.call.0.clone());
                 regular_symbols.push(strike.put.0.clone());
             }
@@ -178,23 +180,16 @@ async fn main() {
             "Converting {} option symbols to DxLink format...",
             regular_symbols.len()
         );
-        for (i, symbol) in regular_symbols.iter().enumerate() {
-            if i % 10 == 0 && i > 0 {
-                println!("Processed {}/{} symbols...", i, regular_symbols.len());
-            }
-
-            match tasty.get_option_info(symbol).await {
-                Ok(option_info) => {
-                    option_symbols.push(option_info.streamer_symbol.0.clone());
-                    symbol_mapping.insert(option_info.streamer_symbol.0.clone(), symbol.clone());
-                }
-                Err(e) => {
-                    println!(
-                        "Warning: Failed to get streamer symbol for {}: {}",
-                        symbol, e
-                    );
+        match tasty.get_equity_options(&regular_symbols).await {
+            Ok(options) => {
+                for option in options {
+                    option_symbols.push(option.streamer_symbol.0.clone());
+                    symbol_mapping.insert(option.streamer_symbol.0, option.symbol.0);
                 }
             }
+            Err(e) => {
+                println!("Warning: Failed to get streamer symbols: {}", e);
+            }
         }
 
         println!(
@@ -283,36 +278,22 @@ async fn main() {
     // Now display the option chain WITH the collected streaming data
     println!("\n=== Options Chain Display ===");
     println!(
-        "Underlying: {} | Root: {} | Type: {}",
+        "Underlying: {} | Root: {} | Type: {:?}",
         chain.underlying_symbol.0, chain.root_symbol.0, chain.option_chain_type
     );
     println!("Shares per Contract: {}", chain.shares_per_contract);
     println!();
 
-    for expiration in &chain.expirations {
-        // Apply DTE filter
-        if let Some(max_dte) = max_dte {
-            if expiration.days_to_expiration > max_dte {
-                continue;
-            }
-        }
-
+    for expiration in &expirations {
         println!(
-            "┌─ Expiration: {} ({} days) - {} Settlement ({})",
+            "┌─ Expiration: {} ({} days) - {:?} Settlement ({:?})",
             expiration.expiration_date,
             expiration.days_to_expiration,
             expiration.expiration_type,
             expiration.settlement_type
         );
 
-        for strike in &expiration.strikes {
-            // Apply strike range filter
-            if let (Some(min), Some(max)) = (strike_min, strike_max) {
-                if strike.strike_price < min || strike.strike_price > max {
-                    continue;
-                }
-            }
-
+        for strike in strikes_in_range(expiration, strike_range) {
             println!("├─ Strike: ${}", strike.strike_price);
 
             // Display call with streaming data if available
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::future::Future;
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -80,6 +81,10 @@ impl<'t> Account<'t> {
         self.inner.account.account_number.clone()
     }
 
+    pub(crate) fn client(&self) -> &'t TastyTrade {
+        self.tasty
+    }
+
     pub async fn balance(&self) -> Result<Balance> {
         let resp = self
             .tasty
@@ -138,6 +143,15 @@ impl<'t> Account<'t> {
         Ok(resp.items)
     }
 
+    pub async fn order(&self, id: OrderId) -> Result<LiveOrderRecord> {
+        self.tasty
+            .get(&format!(
+                "/accounts/{}/orders/{}",
+                self.inner.account.account_number.0, id.0
+            ))
+            .await
+    }
+
     pub async fn dry_run(&self, order: &Order) -> Result<DryRunResult> {
         let resp: DryRunResult = self
             .tasty
@@ -153,6 +167,10 @@ impl<'t> Account<'t> {
     }
 
     pub async fn place_order(&self, order: &Order) -> Result<OrderPlacedResult> {
+        if let Some(guard) = self.tasty.risk_guard().await {
+            guard.check(self, order, None).await?;
+        }
+
         let resp: OrderPlacedResult = self
             .tasty
             .post(
@@ -163,6 +181,23 @@ impl<'t> Account<'t> {
         Ok(resp)
     }
 
+    /// Replace a working order with a new price or structure.
+    pub async fn replace_order(&self, id: OrderId, order: &Order) -> Result<LiveOrderRecord> {
+        if let Some(guard) = self.tasty.risk_guard().await {
+            guard.check(self, order, Some(&id)).await?;
+        }
+
+        self.tasty
+            .put(
+                &format!(
+                    "/accounts/{}/orders/{}",
+                    self.inner.account.account_number.0, id.0
+                ),
+                order,
+            )
+            .await
+    }
+
     pub async fn cancel_order(&self, id: OrderId) -> Result<LiveOrderRecord> {
         self.tasty
             .delete(&format!(
@@ -230,6 +265,49 @@ impl<'t> Account<'t> {
     }
 }
 
+/// Order and portfolio operations shared by live [`Account`]s and simulated
+/// accounts such as [`PaperAccount`](crate::api::paper::PaperAccount), so a
+/// strategy can run unchanged against either.
+pub trait TradingAccount {
+    fn number(&self) -> AccountNumber;
+    fn balance(&self) -> impl Future<Output = Result<Balance>> + Send;
+    fn positions(&self) -> impl Future<Output = Result<Vec<FullPosition>>> + Send;
+    fn live_orders(&self) -> impl Future<Output = Result<Vec<LiveOrderRecord>>> + Send;
+    fn dry_run(&self, order: &Order) -> impl Future<Output = Result<DryRunResult>> + Send;
+    fn place_order(&self, order: &Order) -> impl Future<Output = Result<OrderPlacedResult>> + Send;
+    fn cancel_order(&self, id: OrderId) -> impl Future<Output = Result<LiveOrderRecord>> + Send;
+}
+
+impl TradingAccount for Account<'_> {
+    fn number(&self) -> AccountNumber {
+        Account::number(self)
+    }
+
+    async fn balance(&self) -> Result<Balance> {
+        Account::balance(self).await
+    }
+
+    async fn positions(&self) -> Result<Vec<FullPosition>> {
+        Account::positions(self).await
+    }
+
+    async fn live_orders(&self) -> Result<Vec<LiveOrderRecord>> {
+        Account::live_orders(self).await
+    }
+
+    async fn dry_run(&self, order: &Order) -> Result<DryRunResult> {
+        Account::dry_run(self, order).await
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderPlacedResult> {
+        Account::place_order(self, order).await
+    }
+
+    async fn cancel_order(&self, id: OrderId) -> Result<LiveOrderRecord> {
+        Account::cancel_order(self, id).await
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct Balance {
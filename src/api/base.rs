@@ -51,6 +51,9 @@ pub struct ApiError {
     pub code: Option<String>,
     pub message: String,
     pub errors: Option<Vec<InnerApiError>>,
+    /// HTTP status of the response, when known
+    #[serde(skip)]
+    pub status: Option<u16>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -81,6 +84,22 @@ pub enum TastyError {
     Websocket(#[from] tokio_tungstenite::tungstenite::Error),
     #[error("Transaction Query Error")]
     TransactionQuery(#[from] crate::api::transaction::TransactionQueryError),
+    #[error("Order validation error: {0}")]
+    OrderValidation(#[from] crate::api::order_validation::OrderValidationError),
+    #[error("Order rejected by risk guard: {0}")]
+    RiskRejected(#[from] crate::api::risk::RiskViolation),
+    #[error("Order tracker error: {0}")]
+    OrderTracker(#[from] crate::api::order_tracker::OrderTrackerError),
+    #[error("Paper trading error: {0}")]
+    PaperTrading(#[from] crate::api::paper::PaperTradingError),
+    #[error("Roll error: {0}")]
+    Roll(#[from] crate::api::roll::RollError),
+    #[error("Repricer error: {0}")]
+    Repricer(#[from] crate::api::repricer::RepricerError),
+    #[error("Symbol parse error: {0}")]
+    SymbolParse(#[from] crate::api::option_symbol::SymbolParseError),
+    #[error("Market time error: {0}")]
+    MarketTime(#[from] crate::api::market_time::MarketTimeError),
     #[error("Unexpected response (status {status}): {body}")]
     UnexpectedResponse { status: u16, body: String },
     #[error("Stream disconnected")]
@@ -91,6 +110,32 @@ pub enum TastyError {
     ChannelSend(String),
 }
 
+impl TastyError {
+    /// HTTP status of the failed response, if the error came from one.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            TastyError::Api(error) => error.status,
+            TastyError::UnexpectedResponse { status, .. } => Some(*status),
+            TastyError::Reqwest(error) => error.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Whether the API reported that the requested record does not exist.
+    pub fn is_not_found(&self) -> bool {
+        if self.status() == Some(404) {
+            return true;
+        }
+        match self {
+            TastyError::Api(error) => error
+                .code
+                .as_deref()
+                .is_some_and(|code| code.ends_with("not_found")),
+            _ => false,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, TastyError>;
 
 #[cfg(test)]
@@ -198,6 +243,7 @@ mod tests {
             code: Some("X".to_string()),
             message: "msg".to_string(),
             errors: None,
+            status: None,
         };
 
         assert_eq!(format!("{}", error), "Error Some(\"X\"): msg");
@@ -209,11 +255,34 @@ mod tests {
             code: None,
             message: "msg".to_string(),
             errors: None,
+            status: None,
         };
 
         assert_eq!(format!("{}", error), "Error None: msg");
     }
 
+    #[test]
+    fn test_not_found_classification() {
+        let api = |code: &str, status: Option<u16>| {
+            TastyError::Api(ApiError {
+                code: Some(code.to_string()),
+                message: "msg".to_string(),
+                errors: None,
+                status,
+            })
+        };
+        assert!(api("record_not_found", None).is_not_found());
+        assert!(api("invalid_symbol", Some(404)).is_not_found());
+        assert!(!api("unauthorized", Some(401)).is_not_found());
+        assert_eq!(api("rate_limit", Some(429)).status(), Some(429));
+        assert!(TastyError::UnexpectedResponse {
+            status: 404,
+            body: String::new()
+        }
+        .is_not_found());
+        assert!(!TastyError::Config("x".to_string()).is_not_found());
+    }
+
     #[test]
     fn test_from_tasty_response_for_t() {
         let response = Response {
@@ -0,0 +1,278 @@
+//! Bulk order operations: cancel-all, filtered cancels and batched replaces.
+//!
+//! Requests run concurrently, at most [`BULK_ORDER_CONCURRENCY`] at a time,
+//! and every order gets its own entry in the returned [`BulkOrderReport`] so
+//! one failure never hides the rest.
+
+use futures_util::stream::{self, StreamExt};
+
+use crate::api::accounts::{Account, AccountNumber};
+use crate::api::base::{Result, TastyError};
+use crate::client::TastyTrade;
+
+use super::order::{LiveOrderRecord, Order, OrderId};
+
+/// Maximum number of order requests in flight at once during bulk operations
+pub const BULK_ORDER_CONCURRENCY: usize = 4;
+
+/// Result of a bulk operation for a single order.
+#[derive(Debug)]
+pub struct OrderOutcome {
+    pub account_number: AccountNumber,
+    pub order_id: OrderId,
+    pub result: Result<LiveOrderRecord>,
+}
+
+/// Per-order results of a bulk operation.
+#[derive(Debug, Default)]
+pub struct BulkOrderReport {
+    pub outcomes: Vec<OrderOutcome>,
+    /// Accounts whose working orders could not be listed, for multi-account sweeps
+    pub account_errors: Vec<(AccountNumber, TastyError)>,
+}
+
+impl BulkOrderReport {
+    pub fn succeeded(&self) -> impl Iterator<Item = &OrderOutcome> {
+        self.outcomes.iter().filter(|o| o.result.is_ok())
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &OrderOutcome> {
+        self.outcomes.iter().filter(|o| o.result.is_err())
+    }
+
+    /// True if every order in the batch succeeded (including an empty batch).
+    pub fn is_success(&self) -> bool {
+        self.account_errors.is_empty() && self.outcomes.iter().all(|o| o.result.is_ok())
+    }
+
+    fn extend(&mut self, other: BulkOrderReport) {
+        self.outcomes.extend(other.outcomes);
+        self.account_errors.extend(other.account_errors);
+    }
+}
+
+impl Account<'_> {
+    /// Cancel every working, cancellable order in the account.
+    pub async fn cancel_all_orders(&self) -> Result<BulkOrderReport> {
+        self.cancel_orders_where(|_| true).await
+    }
+
+    /// Cancel every working, cancellable order matching `predicate`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Cancel option orders on SPY older than ten minutes
+    /// let report = account
+    ///     .cancel_orders_where(|o| {
+    ///         o.underlying_symbol.0 == "SPY"
+    ///             && o.has_instrument_type(&InstrumentType::EquityOption)
+    ///             && o.age().is_some_and(|age| age > chrono::Duration::minutes(10))
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn cancel_orders_where<F>(&self, predicate: F) -> Result<BulkOrderReport>
+    where
+        F: Fn(&LiveOrderRecord) -> bool,
+    {
+        let ids = cancellable_ids(self.live_orders().await?, predicate);
+
+        let account_number = self.number();
+        let outcomes = stream::iter(ids)
+            .map(|id| {
+                let account_number = account_number.clone();
+                async move {
+                    OrderOutcome {
+                        account_number,
+                        order_id: id,
+                        result: self.cancel_order(id).await,
+                    }
+                }
+            })
+            .buffer_unordered(BULK_ORDER_CONCURRENCY)
+            .collect()
+            .await;
+        Ok(BulkOrderReport {
+            outcomes,
+            account_errors: vec![],
+        })
+    }
+
+    /// Replace each order id with its new order, returning one outcome per pair.
+    pub async fn cancel_and_replace_many(
+        &self,
+        replacements: Vec<(OrderId, Order)>,
+    ) -> BulkOrderReport {
+        let account_number = self.number();
+        let outcomes = stream::iter(replacements)
+            .map(|(id, order)| {
+                let account_number = account_number.clone();
+                async move {
+                    OrderOutcome {
+                        account_number,
+                        order_id: id,
+                        result: self.replace_order(id, &order).await,
+                    }
+                }
+            })
+            .buffer_unordered(BULK_ORDER_CONCURRENCY)
+            .collect()
+            .await;
+        BulkOrderReport {
+            outcomes,
+            account_errors: vec![],
+        }
+    }
+}
+
+impl TastyTrade {
+    /// Cancel every working order in every account, e.g. to flatten at the close.
+    ///
+    /// Accounts are swept concurrently, at most [`BULK_ORDER_CONCURRENCY`] at
+    /// a time. Accounts whose orders could not be listed end up in
+    /// [`BulkOrderReport::account_errors`] rather than aborting the whole sweep.
+    pub async fn cancel_all_orders(&self) -> Result<BulkOrderReport> {
+        let accounts = self.accounts().await?;
+        let results: Vec<_> = stream::iter(&accounts)
+            .map(|account| async move { (account.number(), account.cancel_all_orders().await) })
+            .buffer_unordered(BULK_ORDER_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut report = BulkOrderReport::default();
+        for (account_number, result) in results {
+            match result {
+                Ok(account_report) => report.extend(account_report),
+                Err(e) => report.account_errors.push((account_number, e)),
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Ids of the working, cancellable orders matching `predicate`.
+fn cancellable_ids<F>(orders: Vec<LiveOrderRecord>, predicate: F) -> Vec<OrderId>
+where
+    F: Fn(&LiveOrderRecord) -> bool,
+{
+    orders
+        .into_iter()
+        .filter(|o| o.status.is_working() && o.cancellable && predicate(o))
+        .map(|o| o.id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::order::{InstrumentType, OrderStatus};
+    use serde_json::json;
+
+    fn record(id: u64, symbol: &str, status: &str, cancellable: bool) -> LiveOrderRecord {
+        serde_json::from_value(json!({
+            "id": id,
+            "account-number": "ACC123",
+            "time-in-force": "Day",
+            "order-type": "Limit",
+            "size": 1,
+            "underlying-symbol": symbol,
+            "price": "1.00",
+            "price-effect": "Debit",
+            "status": status,
+            "cancellable": cancellable,
+            "editable": cancellable,
+            "edited": false
+        }))
+        .unwrap()
+    }
+
+    fn outcome(id: u64, ok: bool) -> OrderOutcome {
+        OrderOutcome {
+            account_number: AccountNumber::from("ACC123"),
+            order_id: OrderId(id),
+            result: if ok {
+                Ok(record(id, "SPY", "Cancelled", false))
+            } else {
+                Err(TastyError::Config("boom".to_string()))
+            },
+        }
+    }
+
+    #[test]
+    fn test_report_partitions_outcomes() {
+        let mut report = BulkOrderReport {
+            outcomes: vec![outcome(1, true), outcome(2, false)],
+            account_errors: vec![],
+        };
+        report.extend(BulkOrderReport {
+            outcomes: vec![outcome(3, true)],
+            account_errors: vec![],
+        });
+
+        assert_eq!(report.succeeded().count(), 2);
+        let failed: Vec<_> = report.failed().map(|o| o.order_id).collect();
+        assert_eq!(failed, vec![OrderId(2)]);
+        assert!(!report.is_success());
+        assert!(BulkOrderReport::default().is_success());
+
+        let unlisted = BulkOrderReport {
+            outcomes: vec![],
+            account_errors: vec![(AccountNumber::from("ACC456"), TastyError::StreamClosed)],
+        };
+        assert!(!unlisted.is_success());
+    }
+
+    #[test]
+    fn test_cancellable_ids_skip_finished_and_locked_orders() {
+        let orders = || {
+            vec![
+                record(1, "SPY", "Live", true),
+                record(2, "SPY", "Filled", false),
+                record(3, "SPY", "Live", false),
+                record(4, "QQQ", "Received", true),
+                record(5, "SPY", "Cancelled", true),
+            ]
+        };
+        let ids = |ids: Vec<OrderId>| ids.into_iter().map(|id| id.0).collect::<Vec<_>>();
+
+        assert_eq!(ids(cancellable_ids(orders(), |_| true)), vec![1, 4]);
+        assert_eq!(
+            ids(cancellable_ids(orders(), |o| o.underlying_symbol.0 == "SPY")),
+            vec![1]
+        );
+        assert!(cancellable_ids(vec![], |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_live_order_record_filters() {
+        let record: LiveOrderRecord = serde_json::from_value(json!({
+            "id": 5,
+            "account-number": "ACC123",
+            "time-in-force": "GTC",
+            "order-type": "Limit",
+            "size": 1,
+            "underlying-symbol": "SPY",
+            "price": "1.00",
+            "price-effect": "Credit",
+            "status": "Live",
+            "cancellable": true,
+            "editable": true,
+            "edited": false,
+            "received-at": "2024-01-02T15:30:00.000+00:00",
+            "legs": [{
+                "instrument-type": "Equity Option",
+                "symbol": "SPY   240119P00450000",
+                "quantity": 1,
+                "remaining-quantity": 1,
+                "action": "Sell to Open",
+                "fills": []
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(record.status, OrderStatus::Live);
+        assert!(record.has_instrument_type(&InstrumentType::EquityOption));
+        assert!(!record.has_instrument_type(&InstrumentType::Equity));
+        assert!(!record.legs[0].action.is_buy());
+        assert!(record.age().unwrap() > chrono::Duration::days(1));
+    }
+}
@@ -0,0 +1,392 @@
+//! Querying a [`NestedOptionChain`].
+//!
+//! [`ExpirationFilter`] narrows the expirations by days to expiration, cycle
+//! and settlement; [`Expiration`] gains strike selection around the money and
+//! by target delta. Deltas come from either source of Greeks:
+//! [`deltas_from_greeks`] for events from
+//! [`DxLinkQuoteStreamer::subscribe_greeks`](super::quote_streaming::DxLinkQuoteStreamer)
+//! and [`deltas_from_market_data`] for [`TastyTrade::fetch_market_data`](crate::TastyTrade).
+//!
+//! # Example
+//! ```ignore
+//! let chain = client.nested_option_chain_for("SPY").await?;
+//! let filter = ExpirationFilter::new()
+//!     .dte(30, 60)
+//!     .expiration_type(ExpirationType::Regular);
+//! for expiration in chain.filter_expirations(&filter) {
+//!     let strikes = expiration.strikes_near_atm(spot, 5);
+//!     let short_put = expiration.strike_by_delta(-0.30, OptionType::Put, &deltas);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use super::instrument::{ExpirationType, OptionType, SettlementType};
+use super::market_data::MarketDataItem;
+use super::option_chain::{Expiration, NestedOptionChain, Strike};
+use super::option_symbol::OptionSymbol;
+use super::order::{AsSymbol, Symbol};
+use super::quote_streaming::GreeksData;
+
+/// Criteria for selecting expirations from a chain. Unset criteria match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpirationFilter {
+    pub min_dte: Option<u64>,
+    pub max_dte: Option<u64>,
+    /// Accepted expiration cycles; empty accepts all
+    pub expiration_types: Vec<ExpirationType>,
+    pub settlement_type: Option<SettlementType>,
+}
+
+impl ExpirationFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep expirations between `min` and `max` days out, inclusive.
+    pub fn dte(self, min: u64, max: u64) -> Self {
+        self.min_dte(min).max_dte(max)
+    }
+
+    pub fn min_dte(mut self, days: u64) -> Self {
+        self.min_dte = Some(days);
+        self
+    }
+
+    pub fn max_dte(mut self, days: u64) -> Self {
+        self.max_dte = Some(days);
+        self
+    }
+
+    /// Accept this expiration cycle. Call repeatedly to accept several.
+    pub fn expiration_type(mut self, expiration_type: ExpirationType) -> Self {
+        if !self.expiration_types.contains(&expiration_type) {
+            self.expiration_types.push(expiration_type);
+        }
+        self
+    }
+
+    pub fn settlement_type(mut self, settlement_type: SettlementType) -> Self {
+        self.settlement_type = Some(settlement_type);
+        self
+    }
+
+    pub fn matches(&self, expiration: &Expiration) -> bool {
+        let dte = expiration.days_to_expiration;
+        self.min_dte.is_none_or(|min| dte >= min)
+            && self.max_dte.is_none_or(|max| dte <= max)
+            && (self.expiration_types.is_empty()
+                || self.expiration_types.contains(&expiration.expiration_type))
+            && self
+                .settlement_type
+                .is_none_or(|s| s == expiration.settlement_type)
+    }
+}
+
+impl NestedOptionChain {
+    /// Expirations matching `filter`, in date order.
+    pub fn filter_expirations(&self, filter: &ExpirationFilter) -> Vec<&Expiration> {
+        self.sorted_expirations()
+            .into_iter()
+            .filter(|e| filter.matches(e))
+            .collect()
+    }
+}
+
+impl Expiration {
+    /// The strike closest to `spot`; ties go to the lower strike.
+    pub fn atm_strike(&self, spot: Decimal) -> Option<&Strike> {
+        self.strikes
+            .iter()
+            .min_by_key(|s| ((s.strike_price - spot).abs(), s.strike_price))
+    }
+
+    /// The at-the-money strike and up to `count` listed strikes on either side, ascending.
+    pub fn strikes_near_atm(&self, spot: Decimal, count: usize) -> Vec<&Strike> {
+        let mut strikes: Vec<&Strike> = self.strikes.iter().collect();
+        strikes.sort_by_key(|s| s.strike_price);
+        let Some(atm) = self.atm_strike(spot) else {
+            return Vec::new();
+        };
+        let index = strikes
+            .iter()
+            .position(|s| std::ptr::eq(*s, atm))
+            .expect("the ATM strike comes from this expiration");
+        let start = index.saturating_sub(count);
+        let end = (index + count + 1).min(strikes.len());
+        strikes[start..end].to_vec()
+    }
+
+    /// Strikes within `percent` (e.g. `5` for 5%) of `spot`, ascending.
+    pub fn strikes_within_percent(&self, spot: Decimal, percent: Decimal) -> Vec<&Strike> {
+        let band = spot * percent.abs() / Decimal::ONE_HUNDRED;
+        self.strikes_between(spot - band, spot + band)
+    }
+
+    /// The strike whose `option_type` contract has the delta closest to `target`.
+    ///
+    /// `deltas` maps OCC symbols to deltas; strikes without one are skipped and
+    /// ties go to the lower strike.
+    pub fn strike_by_delta(
+        &self,
+        target: f64,
+        option_type: OptionType,
+        deltas: &HashMap<Symbol, f64>,
+    ) -> Option<&Strike> {
+        let mut strikes: Vec<&Strike> = self.strikes.iter().collect();
+        strikes.sort_by_key(|s| s.strike_price);
+
+        let mut best: Option<(&Strike, f64)> = None;
+        for strike in strikes {
+            let Some(delta) = deltas.get(strike.symbol(option_type)) else {
+                continue;
+            };
+            let distance = (delta - target).abs();
+            if best.is_none_or(|(_, d)| distance < d) {
+                best = Some((strike, distance));
+            }
+        }
+        best.map(|(strike, _)| strike)
+    }
+}
+
+impl Strike {
+    /// The call or put symbol at this strike.
+    pub fn symbol(&self, option_type: OptionType) -> &Symbol {
+        match option_type {
+            OptionType::Call => &self.call,
+            OptionType::Put => &self.put,
+        }
+    }
+}
+
+/// Deltas keyed by OCC symbol from streamed Greeks events.
+///
+/// Streamer symbols are converted back to OCC form; events for other
+/// instruments or without a delta are skipped. Later events win.
+pub fn deltas_from_greeks<'a>(
+    greeks: impl IntoIterator<Item = &'a GreeksData>,
+) -> HashMap<Symbol, f64> {
+    greeks
+        .into_iter()
+        .filter_map(|g| {
+            let option = OptionSymbol::parse_streamer(&g.symbol).ok()?;
+            Some((option.as_symbol(), g.delta?))
+        })
+        .collect()
+}
+
+/// Deltas keyed by OCC symbol from `/market-data/by-type` items.
+///
+/// Items may carry either the OCC or the streamer form of the symbol; items
+/// that are not options or have no delta are skipped.
+pub fn deltas_from_market_data<'a>(
+    items: impl IntoIterator<Item = &'a MarketDataItem>,
+) -> HashMap<Symbol, f64> {
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let option: OptionSymbol = item.symbol.parse().ok()?;
+            Some((option.as_symbol(), item.delta?.to_f64()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use serde_json::json;
+
+    fn chain() -> NestedOptionChain {
+        let strikes = |date: &str| {
+            ["440", "445", "450", "455", "460"]
+                .iter()
+                .map(|k| {
+                    json!({
+                        "strike-price": k,
+                        "call": format!("SPY   {date}C00{k}000"),
+                        "put": format!("SPY   {date}P00{k}000")
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        serde_json::from_value(json!({
+            "underlying-symbol": "SPY",
+            "root-symbol": "SPY",
+            "option-chain-type": "Standard",
+            "shares-per-contract": 100,
+            "expirations": [
+                {
+                    "expiration-type": "Regular",
+                    "expiration-date": "2024-02-16",
+                    "days-to-expiration": 45,
+                    "settlement-type": "PM",
+                    "strikes": strikes("240216")
+                },
+                {
+                    "expiration-type": "Weekly",
+                    "expiration-date": "2024-01-19",
+                    "days-to-expiration": 17,
+                    "settlement-type": "PM",
+                    "strikes": strikes("240119")
+                },
+                {
+                    "expiration-type": "Quarterly",
+                    "expiration-date": "2024-03-28",
+                    "days-to-expiration": 86,
+                    "settlement-type": "AM",
+                    "strikes": strikes("240328")
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn prices(strikes: &[&Strike]) -> Vec<Decimal> {
+        strikes.iter().map(|s| s.strike_price).collect()
+    }
+
+    #[test]
+    fn test_expiration_filter() {
+        let chain = chain();
+        let dates = |filter: ExpirationFilter| -> Vec<u64> {
+            chain
+                .filter_expirations(&filter)
+                .iter()
+                .map(|e| e.days_to_expiration)
+                .collect()
+        };
+
+        assert_eq!(dates(ExpirationFilter::new()), vec![17, 45, 86]);
+        assert_eq!(dates(ExpirationFilter::new().dte(20, 90)), vec![45, 86]);
+        assert_eq!(dates(ExpirationFilter::new().max_dte(45)), vec![17, 45]);
+        assert_eq!(
+            dates(
+                ExpirationFilter::new()
+                    .expiration_type(ExpirationType::Weekly)
+                    .expiration_type(ExpirationType::Quarterly)
+            ),
+            vec![17, 86]
+        );
+        assert_eq!(
+            dates(ExpirationFilter::new().settlement_type(SettlementType::AM)),
+            vec![86]
+        );
+        assert!(dates(ExpirationFilter::new().min_dte(100)).is_empty());
+    }
+
+    #[test]
+    fn test_strike_selection_around_spot() {
+        let chain = chain();
+        let expiration = chain.nearest_expiration(0).unwrap();
+
+        let spot = Decimal::new(4512, 1);
+        assert_eq!(
+            expiration.atm_strike(spot).unwrap().strike_price,
+            Decimal::from(450)
+        );
+        // Equidistant from 450 and 455: the lower strike wins
+        assert_eq!(
+            expiration
+                .atm_strike(Decimal::new(4525, 1))
+                .unwrap()
+                .strike_price,
+            Decimal::from(450)
+        );
+
+        assert_eq!(
+            prices(&expiration.strikes_near_atm(spot, 1)),
+            vec![Decimal::from(445), Decimal::from(450), Decimal::from(455)]
+        );
+        // Clamped at the edge of the chain
+        assert_eq!(
+            prices(&expiration.strikes_near_atm(Decimal::from(441), 2)),
+            vec![Decimal::from(440), Decimal::from(445), Decimal::from(450)]
+        );
+
+        assert_eq!(
+            prices(&expiration.strikes_within_percent(Decimal::from(450), Decimal::from(2))),
+            vec![Decimal::from(445), Decimal::from(450), Decimal::from(455)]
+        );
+    }
+
+    #[test]
+    fn test_strike_by_delta_from_greeks() {
+        let chain = chain();
+        let expiration = chain
+            .expiration_on(NaiveDate::from_ymd_opt(2024, 2, 16).unwrap())
+            .unwrap();
+
+        let greeks = |symbol: &str, delta: Option<f64>| GreeksData {
+            symbol: symbol.to_string(),
+            volatility: None,
+            delta,
+            gamma: None,
+            theta: None,
+            rho: None,
+            vega: None,
+            event_time: None,
+        };
+        let events = [
+            greeks(".SPY240216P440", Some(-0.22)),
+            greeks(".SPY240216P445", Some(-0.29)),
+            greeks(".SPY240216P450", Some(-0.37)),
+            greeks(".SPY240216C455", Some(0.41)),
+            greeks(".SPY240216C460", None),
+            greeks("SPY", Some(1.0)),
+        ];
+        let deltas = deltas_from_greeks(&events);
+        assert_eq!(deltas.len(), 4);
+        assert_eq!(deltas[&Symbol::from("SPY   240216P00445000")], -0.29);
+
+        let put = expiration
+            .strike_by_delta(-0.30, OptionType::Put, &deltas)
+            .unwrap();
+        assert_eq!(put.strike_price, Decimal::from(445));
+        assert_eq!(put.symbol(OptionType::Put).0, "SPY   240216P00445000");
+
+        let call = expiration
+            .strike_by_delta(0.30, OptionType::Call, &deltas)
+            .unwrap();
+        assert_eq!(call.strike_price, Decimal::from(455));
+
+        assert!(expiration
+            .strike_by_delta(0.30, OptionType::Call, &HashMap::new())
+            .is_none());
+    }
+
+    #[test]
+    fn test_deltas_from_market_data() {
+        let items: Vec<MarketDataItem> = serde_json::from_value(json!([
+            {
+                "symbol": "SPY   240216P00450000",
+                "instrument-type": "Equity Option",
+                "delta": "-0.37"
+            },
+            {
+                "symbol": ".SPY240216C455",
+                "instrument-type": "Equity Option",
+                "delta": 0.41
+            },
+            {
+                "symbol": "SPY",
+                "instrument-type": "Equity",
+                "delta": "1"
+            },
+            {
+                "symbol": "SPY   240216C00460000",
+                "instrument-type": "Equity Option"
+            }
+        ]))
+        .unwrap();
+
+        let deltas = deltas_from_market_data(&items);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[&Symbol::from("SPY   240216P00450000")], -0.37);
+        assert_eq!(deltas[&Symbol::from("SPY   240216C00455000")], 0.41);
+    }
+}
@@ -0,0 +1,349 @@
+//! Historic dividends and earnings reports, and early assignment checks.
+//!
+//! [`early_assignment_risks`] flags short in-the-money calls whose remaining
+//! time value is smaller than an upcoming dividend: holders of those calls
+//! are likely to exercise the day before the ex-date to collect it.
+//!
+//! # Example
+//! ```ignore
+//! let history = client.historic_dividends("AAPL").await?;
+//! let last = history.iter().max_by_key(|d| d.ex_date).unwrap();
+//! let upcoming = UpcomingDividend::new("AAPL", next_ex_date, last.amount);
+//! let positions = account.positions().await?;
+//! let holidays = client.equity_holidays().await?;
+//! for risk in early_assignment_risks(&positions, &[upcoming], &spot_prices, &holidays, today) {
+//!     println!("{} at risk: time value {} < dividend {}", risk.position.symbol.0, risk.extrinsic, risk.dividend);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::api::base::{Items, Result};
+use crate::TastyTrade;
+
+use super::instrument::encode_path_segment;
+use super::market_time::HolidayCalendar;
+use super::option_symbol::OptionSymbol;
+use super::order::{AsSymbol, InstrumentType, Symbol};
+use super::position::{FullPosition, QuantityDirection};
+
+impl TastyTrade {
+    /// Past dividends paid by `symbol`.
+    pub async fn historic_dividends(&self, symbol: impl AsSymbol) -> Result<Vec<DividendEvent>> {
+        let resp: Items<DividendEvent> = self
+            .get(format!(
+                "/market-metrics/historic-corporate-events/dividends/{}",
+                encode_path_segment(&symbol.as_symbol().0)
+            ))
+            .await?;
+        Ok(resp.items)
+    }
+
+    /// Past earnings reports for `symbol`, optionally only those on or after `start_date`.
+    pub async fn earnings_reports(
+        &self,
+        symbol: impl AsSymbol,
+        start_date: Option<NaiveDate>,
+    ) -> Result<Vec<EarningsReport>> {
+        let url = format!(
+            "/market-metrics/historic-corporate-events/earnings-reports/{}",
+            encode_path_segment(&symbol.as_symbol().0)
+        );
+        let start_date = start_date.map(|d| d.format("%Y-%m-%d").to_string());
+        let query: Vec<(&str, &str)> = start_date
+            .as_deref()
+            .map(|d| ("start-date", d))
+            .into_iter()
+            .collect();
+        let resp: Items<EarningsReport> = self.get_with_query(url, &query).await?;
+        Ok(resp.items)
+    }
+}
+
+/// A dividend from the historic corporate events endpoint.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DividendEvent {
+    /// Ex-dividend date
+    #[serde(rename = "occurred-date", alias = "ex-date")]
+    pub ex_date: NaiveDate,
+    /// Cash amount per share
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub amount: Decimal,
+    pub pay_date: Option<NaiveDate>,
+}
+
+/// Reported earnings for one quarter.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EarningsReport {
+    pub occurred_date: NaiveDate,
+    /// Earnings per share
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub eps: Option<Decimal>,
+}
+
+/// A dividend expected on `underlying` going ex on `ex_date`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpcomingDividend {
+    pub underlying: Symbol,
+    pub ex_date: NaiveDate,
+    /// Cash amount per share
+    pub amount: Decimal,
+}
+
+impl UpcomingDividend {
+    pub fn new(underlying: impl AsSymbol, ex_date: NaiveDate, amount: Decimal) -> Self {
+        Self {
+            underlying: underlying.as_symbol(),
+            ex_date,
+            amount,
+        }
+    }
+}
+
+/// A short call likely to be assigned ahead of an ex-dividend date.
+#[derive(Debug, Clone)]
+pub struct AssignmentRisk<'p> {
+    pub position: &'p FullPosition,
+    pub option: OptionSymbol,
+    pub ex_date: NaiveDate,
+    /// Dividend per share
+    pub dividend: Decimal,
+    /// In-the-money amount per share
+    pub intrinsic: Decimal,
+    /// Time value per share left in the call
+    pub extrinsic: Decimal,
+}
+
+/// Short equity calls at risk of early assignment before an upcoming ex-date.
+///
+/// A call is at risk when it is in the money, is still open on the last
+/// trading day before the ex-date, and its time value is below the dividend.
+/// The last trading day skips weekends and the dates in `holidays`, so a
+/// Friday expiration counts against a Monday ex-date. The option price is the
+/// position's `close_price`; `underlying_prices` supplies spot prices by
+/// underlying symbol, and positions without one are skipped. Only ex-dates
+/// after `today` are considered.
+pub fn early_assignment_risks<'p>(
+    positions: &'p [FullPosition],
+    dividends: &[UpcomingDividend],
+    underlying_prices: &HashMap<Symbol, Decimal>,
+    holidays: &HolidayCalendar,
+    today: NaiveDate,
+) -> Vec<AssignmentRisk<'p>> {
+    let mut risks = Vec::new();
+    for position in positions {
+        if !matches!(position.instrument_type, InstrumentType::EquityOption)
+            || !matches!(position.quantity_direction, QuantityDirection::Short)
+        {
+            continue;
+        }
+        let Ok(option) = position.symbol.to_option_symbol() else {
+            continue;
+        };
+        if !option.is_call() {
+            continue;
+        }
+        let Some(spot) = underlying_prices.get(&position.underlying_symbol) else {
+            continue;
+        };
+        let intrinsic = *spot - option.strike;
+        if intrinsic <= Decimal::ZERO {
+            continue;
+        }
+        let extrinsic = (position.close_price - intrinsic).max(Decimal::ZERO);
+
+        // The call has to be alive on the last day to exercise for the dividend
+        let dividend = dividends
+            .iter()
+            .filter(|d| d.underlying == position.underlying_symbol && d.ex_date > today)
+            .filter(|d| option.expiration >= holidays.previous_trading_day(d.ex_date))
+            .min_by_key(|d| d.ex_date);
+        let Some(dividend) = dividend else {
+            continue;
+        };
+
+        if extrinsic < dividend.amount {
+            risks.push(AssignmentRisk {
+                position,
+                option,
+                ex_date: dividend.ex_date,
+                dividend: dividend.amount,
+                intrinsic,
+                extrinsic,
+            });
+        }
+    }
+    risks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::{date, dec};
+    use serde_json::json;
+
+    fn position(symbol: &str, instrument_type: &str, direction: &str, close: &str) -> FullPosition {
+        serde_json::from_value(json!({
+            "account-number": "5WT00000",
+            "symbol": symbol,
+            "instrument-type": instrument_type,
+            "underlying-symbol": "AAPL",
+            "quantity": "1",
+            "quantity-direction": direction,
+            "close-price": close,
+            "average-open-price": "1.00",
+            "average-yearly-market-close-price": "1.00",
+            "average-daily-market-close-price": "1.00",
+            "multiplier": 100,
+            "cost-effect": "Credit",
+            "is-suppressed": false,
+            "is-frozen": false,
+            "restricted-quantity": "0",
+            "realized-day-gain": "0",
+            "realized-day-gain-effect": "None",
+            "realized-day-gain-date": "2024-02-05",
+            "realized-today": "0",
+            "realized-today-effect": "None",
+            "realized-today-date": "2024-02-05",
+            "created-at": "2024-01-02T10:00:00Z",
+            "updated-at": "2024-02-05T16:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_corporate_event_deserialization() {
+        let dividends: Items<DividendEvent> = serde_json::from_value(json!({
+            "items": [
+                { "occurred-date": "2023-11-10", "amount": "0.24" },
+                { "occurred-date": "2023-08-11", "amount": "0.24", "pay-date": "2023-08-17" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(dividends.items[0].ex_date, date(2023, 11, 10));
+        assert_eq!(dividends.items[0].amount, dec("0.24"));
+        assert!(dividends.items[0].pay_date.is_none());
+        assert_eq!(dividends.items[1].pay_date, Some(date(2023, 8, 17)));
+
+        let earnings: Items<EarningsReport> = serde_json::from_value(json!({
+            "items": [
+                { "occurred-date": "2023-11-02", "eps": "1.46" },
+                { "occurred-date": "2023-08-03" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(earnings.items[0].occurred_date, date(2023, 11, 2));
+        assert_eq!(earnings.items[0].eps, Some(dec("1.46")));
+        assert!(earnings.items[1].eps.is_none());
+    }
+
+    #[test]
+    fn test_early_assignment_risks() {
+        let positions = vec![
+            // Deep ITM with 0.05 of time value left: at risk
+            position("AAPL  240216C00170000", "Equity Option", "Short", "15.05"),
+            // ITM but 1.00 of time value: safe
+            position("AAPL  240216C00180000", "Equity Option", "Short", "6.00"),
+            // Out of the money: safe
+            position("AAPL  240216C00190000", "Equity Option", "Short", "0.10"),
+            // Long call and short put are never flagged
+            position("AAPL  240216C00175000", "Equity Option", "Long", "10.00"),
+            position("AAPL  240216P00200000", "Equity Option", "Short", "15.00"),
+            // Expires before the ex-date
+            position("AAPL  240202C00170000", "Equity Option", "Short", "15.00"),
+            position("AAPL", "Equity", "Short", "185.00"),
+        ];
+        let prices = HashMap::from([(Symbol::from("AAPL"), dec("185"))]);
+        let dividends = [UpcomingDividend::new("AAPL", date(2024, 2, 9), dec("0.24"))];
+
+        let holidays = HolidayCalendar::default();
+        let risks =
+            early_assignment_risks(&positions, &dividends, &prices, &holidays, date(2024, 2, 5));
+        assert_eq!(risks.len(), 1);
+        let risk = &risks[0];
+        assert_eq!(risk.position.symbol.0, "AAPL  240216C00170000");
+        assert_eq!(risk.intrinsic, dec("15"));
+        assert_eq!(risk.extrinsic, dec("0.05"));
+        assert_eq!(risk.dividend, dec("0.24"));
+        assert_eq!(risk.ex_date, date(2024, 2, 9));
+
+        // Once the ex-date has passed there is nothing to flag
+        assert!(early_assignment_risks(
+            &positions,
+            &dividends,
+            &prices,
+            &holidays,
+            date(2024, 2, 9)
+        )
+        .is_empty());
+        // Without a spot price the position is skipped
+        assert!(early_assignment_risks(
+            &positions,
+            &dividends,
+            &HashMap::new(),
+            &holidays,
+            date(2024, 2, 5)
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_monday_ex_date_catches_friday_expiration() {
+        let positions = vec![position(
+            "AAPL  240209C00170000",
+            "Equity Option",
+            "Short",
+            "15.05",
+        )];
+        let prices = HashMap::from([(Symbol::from("AAPL"), dec("185"))]);
+        let no_holidays = HolidayCalendar::default();
+
+        // Ex-date Monday 12 Feb: the last day to exercise is Friday 9 Feb
+        let monday = [UpcomingDividend::new(
+            "AAPL",
+            date(2024, 2, 12),
+            dec("0.24"),
+        )];
+        let risks =
+            early_assignment_risks(&positions, &monday, &prices, &no_holidays, date(2024, 2, 5));
+        assert_eq!(risks.len(), 1);
+        assert_eq!(risks[0].ex_date, date(2024, 2, 12));
+
+        // Ex-date Tuesday 20 Feb after Presidents' Day: an expiration on
+        // Friday 16 Feb is the last chance to exercise
+        let positions = vec![position(
+            "AAPL  240216C00170000",
+            "Equity Option",
+            "Short",
+            "15.05",
+        )];
+        let tuesday = [UpcomingDividend::new(
+            "AAPL",
+            date(2024, 2, 20),
+            dec("0.24"),
+        )];
+        let holidays: HolidayCalendar = serde_json::from_value(json!({
+            "market-holidays": ["2024-02-19"]
+        }))
+        .unwrap();
+        assert_eq!(
+            early_assignment_risks(&positions, &tuesday, &prices, &holidays, date(2024, 2, 5))
+                .len(),
+            1
+        );
+        assert!(early_assignment_risks(
+            &positions,
+            &tuesday,
+            &prices,
+            &no_holidays,
+            date(2024, 2, 5)
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+//! Cryptocurrency instruments.
+//!
+//! Crypto pairs such as `BTC/USD` trade around the clock. Their streamer
+//! symbols carry a venue suffix (`BTC/USD:CXTALP`), so resolve them through
+//! [`Cryptocurrency::streamer_symbol`] or [`TastyTrade::streamer_symbol_for`]
+//! rather than subscribing with the trading symbol.
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::api::base::{Items, Result};
+use crate::TastyTrade;
+
+use super::instrument::encode_path_segment;
+use super::order::{AsSymbol, Symbol};
+use super::order_legs::CRYPTO_QUANTITY_SCALE;
+use super::quote_streaming::DxFeedSymbol;
+
+impl TastyTrade {
+    /// List cryptocurrency instruments, or only `symbols` if any are given.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let all = client.get_cryptocurrencies(&[] as &[&str]).await?;
+    /// let btc_eth = client.get_cryptocurrencies(&["BTC/USD", "ETH/USD"]).await?;
+    /// ```
+    pub async fn get_cryptocurrencies(
+        &self,
+        symbols: &[impl AsSymbol],
+    ) -> Result<Vec<Cryptocurrency>> {
+        let symbols: Vec<Symbol> = symbols.iter().map(AsSymbol::as_symbol).collect();
+        let query: Vec<(&str, &str)> = symbols
+            .iter()
+            .map(|symbol| ("symbol[]", symbol.0.as_str()))
+            .collect();
+        let resp: Items<Cryptocurrency> = self
+            .get_with_query("/instruments/cryptocurrencies", &query)
+            .await?;
+        Ok(resp.items)
+    }
+
+    /// Get a single cryptocurrency instrument such as `BTC/USD`.
+    pub async fn get_cryptocurrency(&self, symbol: impl AsSymbol) -> Result<Cryptocurrency> {
+        self.get(format!(
+            "/instruments/cryptocurrencies/{}",
+            encode_path_segment(&symbol.as_symbol().0)
+        ))
+        .await
+    }
+}
+
+/// A cryptocurrency pair from `/instruments/cryptocurrencies`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Cryptocurrency {
+    pub id: Option<u64>,
+
+    /// Trading symbol (e.g., "BTC/USD")
+    pub symbol: Symbol,
+
+    /// Short name (e.g., "Bitcoin")
+    pub short_description: Option<String>,
+
+    pub description: Option<String>,
+
+    pub active: Option<bool>,
+
+    pub is_closing_only: Option<bool>,
+
+    /// Minimum price increment
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub tick_size: Option<Decimal>,
+
+    /// Symbol used for DxLink streaming subscriptions (e.g., "BTC/USD:CXTALP")
+    pub streamer_symbol: DxFeedSymbol,
+
+    /// Per-venue routing symbols and precision limits
+    #[serde(default)]
+    pub destination_venue_symbols: Vec<DestinationVenueSymbol>,
+}
+
+impl Cryptocurrency {
+    /// Decimal places accepted in order quantities.
+    ///
+    /// Uses the most precise routable venue, falling back to
+    /// [`CRYPTO_QUANTITY_SCALE`] when the API reports none.
+    pub fn quantity_precision(&self) -> u32 {
+        self.routable_venues()
+            .filter_map(|v| v.max_quantity_precision)
+            .max()
+            .unwrap_or(CRYPTO_QUANTITY_SCALE)
+    }
+
+    /// Decimal places accepted in order prices, derived from the venues or the tick size.
+    pub fn price_precision(&self) -> u32 {
+        self.routable_venues()
+            .filter_map(|v| v.max_price_precision)
+            .max()
+            .or_else(|| self.tick_size.map(|t| t.normalize().scale()))
+            .unwrap_or(2)
+    }
+
+    fn routable_venues(&self) -> impl Iterator<Item = &DestinationVenueSymbol> {
+        self.destination_venue_symbols
+            .iter()
+            .filter(|v| v.routable.unwrap_or(true))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DestinationVenueSymbol {
+    pub id: Option<u64>,
+    /// Venue-specific symbol (e.g., "BTCUSD")
+    pub symbol: String,
+    pub destination_venue: Option<String>,
+    pub max_quantity_precision: Option<u32>,
+    pub max_price_precision: Option<u32>,
+    pub routable: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::dec;
+    use serde_json::json;
+
+    #[test]
+    fn test_cryptocurrency_deserialization() {
+        let json = json!({
+            "id": 1,
+            "symbol": "BTC/USD",
+            "instrument-type": "Cryptocurrency",
+            "short-description": "Bitcoin",
+            "description": "Bitcoin to USD",
+            "is-closing-only": false,
+            "active": true,
+            "tick-size": "0.01",
+            "streamer-symbol": "BTC/USD:CXTALP",
+            "destination-venue-symbols": [
+                {
+                    "id": 1,
+                    "symbol": "BTCUSD",
+                    "destination-venue": "CISCO",
+                    "max-quantity-precision": 8,
+                    "max-price-precision": 2,
+                    "routable": true
+                },
+                {
+                    "id": 2,
+                    "symbol": "BTC-USD",
+                    "destination-venue": "OTHER",
+                    "max-quantity-precision": 10,
+                    "max-price-precision": 4,
+                    "routable": false
+                }
+            ]
+        });
+
+        let crypto: Cryptocurrency = serde_json::from_value(json).unwrap();
+        assert_eq!(crypto.symbol.0, "BTC/USD");
+        assert_eq!(crypto.streamer_symbol.0, "BTC/USD:CXTALP");
+        assert_eq!(crypto.tick_size, Some(dec("0.01")));
+        // The unroutable venue does not count
+        assert_eq!(crypto.quantity_precision(), 8);
+        assert_eq!(crypto.price_precision(), 2);
+    }
+
+    #[test]
+    fn test_precision_fallbacks() {
+        let crypto: Cryptocurrency = serde_json::from_value(json!({
+            "symbol": "DOGE/USD",
+            "tick-size": "0.000001",
+            "streamer-symbol": "DOGE/USD:CXTALP"
+        }))
+        .unwrap();
+        assert_eq!(crypto.quantity_precision(), CRYPTO_QUANTITY_SCALE);
+        assert_eq!(crypto.price_precision(), 6);
+    }
+}
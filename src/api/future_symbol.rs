@@ -0,0 +1,426 @@
+//! Structured futures and futures option symbols.
+//!
+//! [`FutureSymbol`] handles contracts such as `/ESZ4` and their streamer form
+//! `/ESZ24:XCME`; [`FutureOptionSymbol`] handles options on them such as
+//! `./ESZ4 EW4Z4 241220P5000`.
+//!
+//! Trading symbols carry a one-digit year. It is expanded to the first
+//! matching year no earlier than last year, relative to today or to the date
+//! given to the `*_relative_to` parsers.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use rust_decimal::Decimal;
+
+use super::instrument::OptionType;
+use super::option_symbol::SymbolParseError;
+use super::order::{AsSymbol, Symbol};
+use super::quote_streaming::DxFeedSymbol;
+
+/// Futures month codes, January through December
+const MONTH_CODES: [char; 12] = ['F', 'G', 'H', 'J', 'K', 'M', 'N', 'Q', 'U', 'V', 'X', 'Z'];
+
+/// Calendar month (1-12) for a futures month code.
+pub fn month_from_code(code: char) -> Option<u32> {
+    MONTH_CODES
+        .iter()
+        .position(|&c| c == code)
+        .map(|i| i as u32 + 1)
+}
+
+/// Futures month code for a calendar month (1-12).
+pub fn month_code(month: u32) -> Option<char> {
+    MONTH_CODES.get(month.checked_sub(1)? as usize).copied()
+}
+
+/// Split `ESZ4` / `EW4Z24` into root, month and full year.
+fn parse_contract_code(code: &str, today: NaiveDate) -> Option<(String, u32, i32)> {
+    if !code.is_ascii() {
+        return None;
+    }
+    let digits = code.len() - code.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    if !(1..=2).contains(&digits) || code.len() < digits + 2 {
+        return None;
+    }
+    let (rest, year) = code.split_at(code.len() - digits);
+    let (root, month) = rest.split_at(rest.len() - 1);
+    let month = month_from_code(month.chars().next()?)?;
+    if !root
+        .chars()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    {
+        return None;
+    }
+    let year: i32 = year.parse().ok()?;
+    let year = if digits == 2 {
+        2000 + year
+    } else {
+        let mut full = today.year() - today.year().rem_euclid(10) + year;
+        if full < today.year() - 1 {
+            full += 10;
+        }
+        full
+    };
+    Some((root.to_string(), month, year))
+}
+
+/// A futures contract such as `/ESZ4`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FutureSymbol {
+    /// Product root without the slash (e.g., "ES")
+    pub root: String,
+    /// Contract month (1-12)
+    pub month: u32,
+    /// Full contract year (e.g., 2024)
+    pub year: i32,
+}
+
+impl FutureSymbol {
+    pub fn parse(symbol: &str) -> Result<Self, SymbolParseError> {
+        Self::parse_relative_to(symbol, Utc::now().date_naive())
+    }
+
+    /// Parse `/ESZ4`, expanding a one-digit year relative to `today`.
+    pub fn parse_relative_to(symbol: &str, today: NaiveDate) -> Result<Self, SymbolParseError> {
+        let invalid = || SymbolParseError::InvalidFuture(symbol.to_string());
+        let code = symbol.strip_prefix('/').ok_or_else(invalid)?;
+        let (root, month, year) = parse_contract_code(code, today).ok_or_else(invalid)?;
+        Ok(Self { root, month, year })
+    }
+
+    /// Parse the streamer form `/ESZ24:XCME`, returning the contract and exchange code.
+    pub fn parse_streamer(symbol: &str) -> Result<(Self, String), SymbolParseError> {
+        let invalid = || SymbolParseError::InvalidFuture(symbol.to_string());
+        let (contract, exchange) = symbol.split_once(':').ok_or_else(invalid)?;
+        if exchange.is_empty() {
+            return Err(invalid());
+        }
+        let future = Self::parse(contract).map_err(|_| invalid())?;
+        Ok((future, exchange.to_string()))
+    }
+
+    /// Futures month code (e.g., 'Z' for December).
+    pub fn month_code(&self) -> char {
+        month_code(self.month).unwrap_or('?')
+    }
+
+    /// First day of the contract month.
+    pub fn contract_month(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1).unwrap_or_default()
+    }
+
+    /// Trading symbol with a one-digit year, e.g. `/ESZ4`.
+    pub fn to_symbol(&self) -> String {
+        format!(
+            "/{}{}{}",
+            self.root,
+            self.month_code(),
+            self.year.rem_euclid(10)
+        )
+    }
+
+    /// Streamer symbol with a two-digit year and exchange, e.g. `/ESZ24:XCME`.
+    ///
+    /// The exchange code is the product's `streamer_exchange_code`.
+    pub fn to_streamer_symbol(&self, exchange_code: &str) -> DxFeedSymbol {
+        DxFeedSymbol(format!(
+            "/{}{}{:02}:{}",
+            self.root,
+            self.month_code(),
+            self.year.rem_euclid(100),
+            exchange_code
+        ))
+    }
+}
+
+impl fmt::Display for FutureSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_symbol())
+    }
+}
+
+impl FromStr for FutureSymbol {
+    type Err = SymbolParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl AsSymbol for FutureSymbol {
+    fn as_symbol(&self) -> Symbol {
+        Symbol(self.to_symbol())
+    }
+}
+
+impl AsSymbol for &FutureSymbol {
+    fn as_symbol(&self) -> Symbol {
+        (*self).as_symbol()
+    }
+}
+
+impl TryFrom<&Symbol> for FutureSymbol {
+    type Error = SymbolParseError;
+
+    fn try_from(symbol: &Symbol) -> Result<Self, Self::Error> {
+        Self::parse(&symbol.0)
+    }
+}
+
+impl TryFrom<&DxFeedSymbol> for FutureSymbol {
+    type Error = SymbolParseError;
+
+    /// Parse a streamer symbol, discarding the exchange code.
+    fn try_from(symbol: &DxFeedSymbol) -> Result<Self, Self::Error> {
+        Self::parse_streamer(&symbol.0).map(|(future, _)| future)
+    }
+}
+
+/// An option on a futures contract such as `./ESZ4 EW4Z4 241220P5000`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FutureOptionSymbol {
+    /// Futures contract delivered on exercise
+    pub underlying: FutureSymbol,
+    /// Option product root (e.g., "EW4")
+    pub option_root: String,
+    /// Option contract month (1-12), which can differ from the future's
+    pub option_month: u32,
+    /// Full option contract year
+    pub option_year: i32,
+    pub expiration: NaiveDate,
+    pub option_type: OptionType,
+    pub strike: Decimal,
+}
+
+impl FutureOptionSymbol {
+    pub fn parse(symbol: &str) -> Result<Self, SymbolParseError> {
+        Self::parse_relative_to(symbol, Utc::now().date_naive())
+    }
+
+    /// Parse `./ESZ4 EW4Z4 241220P5000`, expanding one-digit years relative to `today`.
+    pub fn parse_relative_to(symbol: &str, today: NaiveDate) -> Result<Self, SymbolParseError> {
+        let invalid = || SymbolParseError::InvalidFutureOption(symbol.to_string());
+        let rest = symbol.strip_prefix('.').ok_or_else(invalid)?;
+        let mut parts = rest.split_whitespace();
+        let (Some(future), Some(contract), Some(series), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let underlying = FutureSymbol::parse_relative_to(future, today).map_err(|_| invalid())?;
+        let (option_root, option_month, option_year) =
+            parse_contract_code(contract, today).ok_or_else(invalid)?;
+
+        // yymmdd + C/P + strike
+        if !series.is_ascii() || series.len() < 8 {
+            return Err(invalid());
+        }
+        let date = &series[..6];
+        if !date.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let expiration = NaiveDate::parse_from_str(date, "%y%m%d").map_err(|_| invalid())?;
+        let option_type = match &series[6..7] {
+            "C" => OptionType::Call,
+            "P" => OptionType::Put,
+            _ => return Err(invalid()),
+        };
+        let strike = Decimal::from_str(&series[7..]).map_err(|_| invalid())?;
+        if strike.is_sign_negative() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            underlying,
+            option_root,
+            option_month,
+            option_year,
+            expiration,
+            option_type,
+            strike: strike.normalize(),
+        })
+    }
+
+    /// Option contract code with a one-digit year, e.g. `EW4Z4`.
+    pub fn option_contract(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.option_root,
+            month_code(self.option_month).unwrap_or('?'),
+            self.option_year.rem_euclid(10)
+        )
+    }
+
+    /// First day of the option contract month.
+    pub fn contract_month(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.option_year, self.option_month, 1).unwrap_or_default()
+    }
+
+    pub fn is_call(&self) -> bool {
+        self.option_type == OptionType::Call
+    }
+
+    /// Trading symbol, e.g. `./ESZ4 EW4Z4 241220P5000`.
+    pub fn to_symbol(&self) -> String {
+        format!(
+            ".{} {} {}{}{}",
+            self.underlying.to_symbol(),
+            self.option_contract(),
+            self.expiration.format("%y%m%d"),
+            match self.option_type {
+                OptionType::Call => 'C',
+                OptionType::Put => 'P',
+            },
+            self.strike.normalize()
+        )
+    }
+
+    /// Streamer symbol, e.g. `./EW4Z24P5000:XCME`.
+    pub fn to_streamer_symbol(&self, exchange_code: &str) -> DxFeedSymbol {
+        DxFeedSymbol(format!(
+            "./{}{}{:02}{}{}:{}",
+            self.option_root,
+            month_code(self.option_month).unwrap_or('?'),
+            self.option_year.rem_euclid(100),
+            match self.option_type {
+                OptionType::Call => 'C',
+                OptionType::Put => 'P',
+            },
+            self.strike.normalize(),
+            exchange_code
+        ))
+    }
+}
+
+impl fmt::Display for FutureOptionSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_symbol())
+    }
+}
+
+impl FromStr for FutureOptionSymbol {
+    type Err = SymbolParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl AsSymbol for FutureOptionSymbol {
+    fn as_symbol(&self) -> Symbol {
+        Symbol(self.to_symbol())
+    }
+}
+
+impl AsSymbol for &FutureOptionSymbol {
+    fn as_symbol(&self) -> Symbol {
+        (*self).as_symbol()
+    }
+}
+
+impl TryFrom<&Symbol> for FutureOptionSymbol {
+    type Error = SymbolParseError;
+
+    fn try_from(symbol: &Symbol) -> Result<Self, Self::Error> {
+        Self::parse(&symbol.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::date;
+
+    #[test]
+    fn test_month_codes() {
+        assert_eq!(month_from_code('F'), Some(1));
+        assert_eq!(month_from_code('Z'), Some(12));
+        assert_eq!(month_from_code('A'), None);
+        assert_eq!(month_code(3), Some('H'));
+        assert_eq!(month_code(0), None);
+        assert_eq!(month_code(13), None);
+    }
+
+    #[test]
+    fn test_parse_future() {
+        let today = date(2024, 10, 1);
+        let es = FutureSymbol::parse_relative_to("/ESZ4", today).unwrap();
+        assert_eq!(es.root, "ES");
+        assert_eq!(es.month, 12);
+        assert_eq!(es.year, 2024);
+        assert_eq!(es.contract_month(), date(2024, 12, 1));
+        assert_eq!(es.to_symbol(), "/ESZ4");
+        assert_eq!(es.to_streamer_symbol("XCME").0, "/ESZ24:XCME");
+
+        // Rolls into the next decade once the year is well past
+        let cl = FutureSymbol::parse_relative_to("/CLF2", date(2029, 6, 1)).unwrap();
+        assert_eq!(cl.year, 2032);
+        let last_year = FutureSymbol::parse_relative_to("/CLZ8", date(2029, 6, 1)).unwrap();
+        assert_eq!(last_year.year, 2028);
+
+        let two_digit = FutureSymbol::parse_relative_to("/MESH25", today).unwrap();
+        assert_eq!((two_digit.root.as_str(), two_digit.year), ("MES", 2025));
+
+        for bad in ["ESZ4", "/ESA4", "/Z4", "/ESZ", "/ESZ123", "/es z4"] {
+            assert!(
+                FutureSymbol::parse_relative_to(bad, today).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_future_streamer_round_trip() {
+        let (future, exchange) = FutureSymbol::parse_streamer("/ESZ24:XCME").unwrap();
+        assert_eq!(exchange, "XCME");
+        assert_eq!(future.year, 2024);
+        assert_eq!(future.to_streamer_symbol(&exchange).0, "/ESZ24:XCME");
+        assert_eq!(
+            FutureSymbol::try_from(&DxFeedSymbol("/CLF25:XNYM".to_string()))
+                .unwrap()
+                .root,
+            "CL"
+        );
+        assert!(FutureSymbol::parse_streamer("/ESZ24").is_err());
+        assert_eq!(future.as_symbol(), Symbol::from("/ESZ4"));
+    }
+
+    #[test]
+    fn test_parse_future_option() {
+        let today = date(2024, 10, 1);
+        let option =
+            FutureOptionSymbol::parse_relative_to("./ESZ4 EW4Z4 241220P5000", today).unwrap();
+        assert_eq!(option.underlying.to_symbol(), "/ESZ4");
+        assert_eq!(option.option_root, "EW4");
+        assert_eq!(option.option_contract(), "EW4Z4");
+        assert_eq!(option.contract_month(), date(2024, 12, 1));
+        assert_eq!(option.expiration, date(2024, 12, 20));
+        assert!(!option.is_call());
+        assert_eq!(option.strike, Decimal::from(5000));
+        assert_eq!(option.to_symbol(), "./ESZ4 EW4Z4 241220P5000");
+        assert_eq!(option.to_streamer_symbol("XCME").0, "./EW4Z24P5000:XCME");
+
+        let crude =
+            FutureOptionSymbol::parse_relative_to("./CLZ4 LO1X4 241104C70.5", today).unwrap();
+        assert_eq!(crude.option_month, 11);
+        assert_eq!(crude.strike, Decimal::from_str("70.5").unwrap());
+        assert_eq!(crude.to_symbol(), "./CLZ4 LO1X4 241104C70.5");
+
+        for bad in [
+            "/ESZ4 EW4Z4 241220P5000",
+            "./ESZ4 EW4Z4",
+            "./ESZ4 EW4Z4 241220X5000",
+            "./ESZ4 EW4Z4 241320P5000",
+            "./ESZ4 EW4Z4 241220P",
+            "./ESZ4 EW4Z4 241220P5000 extra",
+        ] {
+            assert!(
+                FutureOptionSymbol::parse_relative_to(bad, today).is_err(),
+                "{bad}"
+            );
+        }
+    }
+}
@@ -0,0 +1,498 @@
+//! Futures contracts, futures products and nested futures option chains.
+//!
+//! # Example
+//! ```ignore
+//! let chain = client.nested_futures_option_chain_for("ES").await?;
+//! for (future, expirations) in chain.expirations_by_future() {
+//!     println!("{}: {} option expirations", future.0, expirations.len());
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::api::base::{Items, Result};
+use crate::TastyTrade;
+
+use super::instrument::{
+    encode_path_segment, ExerciseStyle, ExpirationType, SettlementType, TickSize,
+};
+use super::order::{AsSymbol, Symbol};
+use super::quote_streaming::DxFeedSymbol;
+
+impl TastyTrade {
+    /// List futures contracts for the given product codes (e.g. `["ES", "CL"]`).
+    pub async fn get_futures(&self, product_codes: &[&str]) -> Result<Vec<Future>> {
+        let query: Vec<(&str, &str)> = product_codes
+            .iter()
+            .map(|code| ("product-code[]", *code))
+            .collect();
+        let resp: Items<Future> = self.get_with_query("/instruments/futures", &query).await?;
+        Ok(resp.items)
+    }
+
+    /// Get a single futures contract such as `/ESZ4`.
+    pub async fn get_future(&self, symbol: impl AsSymbol) -> Result<Future> {
+        let symbol = symbol.as_symbol();
+        self.get(format!(
+            "/instruments/futures/{}",
+            encode_path_segment(symbol.0.trim_start_matches('/'))
+        ))
+        .await
+    }
+
+    /// List every futures product (ES, CL, ZN, ...).
+    pub async fn future_products(&self) -> Result<Vec<FutureProduct>> {
+        let resp: Items<FutureProduct> = self.get("/instruments/future-products").await?;
+        Ok(resp.items)
+    }
+
+    /// Get a single futures product by exchange and code, e.g. `("CME", "ES")`.
+    pub async fn future_product(&self, exchange: &str, code: &str) -> Result<FutureProduct> {
+        self.get(format!(
+            "/instruments/future-products/{}/{}",
+            encode_path_segment(exchange),
+            encode_path_segment(code)
+        ))
+        .await
+    }
+
+    /// List every futures option product (ES, EW1, LO, ...).
+    pub async fn future_option_products(&self) -> Result<Vec<FutureOptionProduct>> {
+        let resp: Items<FutureOptionProduct> =
+            self.get("/instruments/future-option-products").await?;
+        Ok(resp.items)
+    }
+
+    /// Get the nested futures option chain for a product code such as `ES` or `CL`.
+    pub async fn nested_futures_option_chain_for(
+        &self,
+        product_code: &str,
+    ) -> Result<FuturesOptionChain> {
+        let code = product_code.trim_start_matches('/');
+        self.get(format!(
+            "/futures-option-chains/{}/nested",
+            encode_path_segment(code)
+        ))
+        .await
+    }
+}
+
+/// A futures contract from `/instruments/futures`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Future {
+    /// Contract symbol (e.g., "/ESZ4")
+    pub symbol: Symbol,
+
+    /// Product code without the slash (e.g., "ES")
+    pub product_code: String,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub contract_size: Option<Decimal>,
+
+    /// Minimum price increment
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub tick_size: Decimal,
+
+    /// Dollar value of a one-point move
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub notional_multiplier: Decimal,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub display_factor: Option<Decimal>,
+
+    pub last_trade_date: Option<NaiveDate>,
+
+    pub expiration_date: NaiveDate,
+
+    pub closing_only_date: Option<NaiveDate>,
+
+    pub active: Option<bool>,
+
+    /// Whether this is the product's most liquid (front) contract
+    #[serde(default)]
+    pub active_month: bool,
+
+    #[serde(default)]
+    pub next_active_month: bool,
+
+    pub is_closing_only: Option<bool>,
+
+    pub is_tradeable: Option<bool>,
+
+    pub stops_trading_at: Option<DateTime<Utc>>,
+
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Exchange (e.g., "CME")
+    pub exchange: Option<String>,
+
+    /// Contract the product rolls to when this one stops being the active month
+    pub roll_target_symbol: Option<Symbol>,
+
+    /// Symbol used for DxLink streaming subscriptions (e.g., "/ESZ24:XCME")
+    pub streamer_symbol: DxFeedSymbol,
+
+    pub tick_sizes: Option<Vec<TickSize>>,
+
+    pub option_tick_sizes: Option<Vec<TickSize>>,
+
+    pub future_product: Option<FutureProduct>,
+}
+
+/// Contract specification shared by every month of a futures product.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FutureProduct {
+    /// Root symbol with slash (e.g., "/ES")
+    pub root_symbol: Symbol,
+
+    /// Product code (e.g., "ES")
+    pub code: String,
+
+    pub description: Option<String>,
+
+    /// Exchange (e.g., "CME")
+    pub exchange: String,
+
+    /// Product type (e.g., "Financial", "Physical")
+    pub product_type: Option<String>,
+
+    /// Market sector (e.g., "Equity Index", "Energy")
+    pub market_sector: Option<String>,
+
+    /// Listed contract month codes (e.g., ["H", "M", "U", "Z"])
+    #[serde(default)]
+    pub listed_months: Vec<String>,
+
+    /// Month codes that become the active month
+    #[serde(default)]
+    pub active_months: Vec<String>,
+
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub notional_multiplier: Decimal,
+
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub tick_size: Decimal,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub display_factor: Option<Decimal>,
+
+    pub streamer_exchange_code: Option<String>,
+
+    pub cash_settled: Option<bool>,
+
+    pub small_notional: Option<bool>,
+}
+
+/// Specification of an option product on futures (e.g., ES quarterlies, EW1 weeklies).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FutureOptionProduct {
+    /// Option root (e.g., "EW1")
+    pub root_symbol: String,
+
+    pub code: Option<String>,
+
+    /// Exchange (e.g., "CME")
+    pub exchange: String,
+
+    pub product_type: Option<String>,
+
+    pub expiration_type: Option<ExpirationType>,
+
+    pub settlement_delay_days: Option<u64>,
+
+    pub market_sector: Option<String>,
+
+    pub cash_settled: Option<bool>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub display_factor: Option<Decimal>,
+
+    pub future_product: Option<FutureProduct>,
+}
+
+/// Response of `/futures-option-chains/{product}/nested`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FuturesOptionChain {
+    /// Underlying futures contracts the options are written on
+    pub futures: Vec<NestedFuture>,
+
+    /// One entry per option root (e.g., ES and EW1..EW4 for /ES)
+    pub option_chains: Vec<NestedFutureOptionChain>,
+}
+
+impl FuturesOptionChain {
+    /// Every option expiration across all roots, grouped by underlying future
+    /// and sorted by date within each group.
+    pub fn expirations_by_future(&self) -> BTreeMap<&Symbol, Vec<&FutureOptionExpiration>> {
+        let mut grouped: BTreeMap<&Symbol, Vec<&FutureOptionExpiration>> = BTreeMap::new();
+        for expiration in self.option_chains.iter().flat_map(|c| &c.expirations) {
+            grouped
+                .entry(&expiration.underlying_symbol)
+                .or_default()
+                .push(expiration);
+        }
+        for expirations in grouped.values_mut() {
+            expirations.sort_by_key(|e| e.expiration_date);
+        }
+        grouped
+    }
+
+    /// Option expirations on a single futures contract such as `/ESZ4`.
+    pub fn expirations_for(&self, future: impl AsSymbol) -> Vec<&FutureOptionExpiration> {
+        let future = future.as_symbol();
+        self.expirations_by_future()
+            .remove(&future)
+            .unwrap_or_default()
+    }
+
+    /// The product's active (front) month contract.
+    pub fn active_future(&self) -> Option<&NestedFuture> {
+        self.futures.iter().find(|f| f.active_month)
+    }
+}
+
+/// A futures contract as listed inside a nested option chain.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NestedFuture {
+    pub symbol: Symbol,
+    pub root_symbol: Symbol,
+    pub expiration_date: NaiveDate,
+    pub days_to_expiration: u64,
+    #[serde(default)]
+    pub active_month: bool,
+    #[serde(default)]
+    pub next_active_month: bool,
+    pub stops_trading_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NestedFutureOptionChain {
+    /// Futures product root (e.g., "/ES")
+    pub underlying_symbol: Symbol,
+    /// Option root (e.g., "EW1")
+    pub root_symbol: String,
+    pub exercise_style: Option<ExerciseStyle>,
+    pub expirations: Vec<FutureOptionExpiration>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FutureOptionExpiration {
+    /// Futures contract these options deliver (e.g., "/ESZ4")
+    pub underlying_symbol: Symbol,
+    /// Futures product root (e.g., "/ES")
+    pub root_symbol: Symbol,
+    /// Option root (e.g., "EW4")
+    pub option_root_symbol: String,
+    /// Option contract code (e.g., "EW4U4")
+    pub option_contract_symbol: String,
+    pub expiration_date: NaiveDate,
+    pub days_to_expiration: u64,
+    pub expiration_type: ExpirationType,
+    pub settlement_type: Option<SettlementType>,
+    /// Dollar value of a one-point move in the option
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub notional_value: Decimal,
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub display_factor: Option<Decimal>,
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub strike_factor: Option<Decimal>,
+    pub stops_trading_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tick_sizes: Vec<TickSize>,
+    pub strikes: Vec<FutureOptionStrike>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FutureOptionStrike {
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub strike_price: Decimal,
+    pub call: Symbol,
+    pub call_streamer_symbol: DxFeedSymbol,
+    pub put: Symbol,
+    pub put_streamer_symbol: DxFeedSymbol,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::dec;
+    use serde_json::json;
+
+    fn es_product() -> serde_json::Value {
+        json!({
+            "root-symbol": "/ES",
+            "code": "ES",
+            "description": "E-Mini S&P 500",
+            "exchange": "CME",
+            "product-type": "Financial",
+            "market-sector": "Equity Index",
+            "listed-months": ["H", "M", "U", "Z"],
+            "active-months": ["H", "M", "U", "Z"],
+            "notional-multiplier": "50.0",
+            "tick-size": "0.25",
+            "display-factor": "0.01",
+            "streamer-exchange-code": "XCME",
+            "cash-settled": true,
+            "small-notional": false
+        })
+    }
+
+    #[test]
+    fn test_future_deserialization() {
+        let json = json!({
+            "symbol": "/ESZ4",
+            "product-code": "ES",
+            "contract-size": "50.0",
+            "tick-size": "0.25",
+            "notional-multiplier": "50.0",
+            "display-factor": "0.01",
+            "last-trade-date": "2024-12-20",
+            "expiration-date": "2024-12-20",
+            "closing-only-date": "2024-12-20",
+            "active": true,
+            "active-month": true,
+            "next-active-month": false,
+            "is-closing-only": false,
+            "is-tradeable": true,
+            "stops-trading-at": "2024-12-20T14:30:00.000+00:00",
+            "expires-at": "2024-12-20T14:30:00.000+00:00",
+            "exchange": "CME",
+            "roll-target-symbol": "/ESH5",
+            "streamer-symbol": "/ESZ24:XCME",
+            "tick-sizes": [{ "value": "0.25" }],
+            "future-product": es_product()
+        });
+
+        let future: Future = serde_json::from_value(json).unwrap();
+        assert_eq!(future.symbol.0, "/ESZ4");
+        assert_eq!(future.tick_size, dec("0.25"));
+        assert_eq!(future.notional_multiplier, dec("50"));
+        assert_eq!(
+            future.expiration_date,
+            NaiveDate::from_ymd_opt(2024, 12, 20).unwrap()
+        );
+        assert!(future.active_month);
+        assert_eq!(future.roll_target_symbol, Some(Symbol::from("/ESH5")));
+        assert_eq!(future.streamer_symbol.0, "/ESZ24:XCME");
+        assert_eq!(future.future_product.unwrap().code, "ES");
+    }
+
+    #[test]
+    fn test_future_product_deserialization() {
+        let product: FutureProduct = serde_json::from_value(es_product()).unwrap();
+        assert_eq!(product.root_symbol.0, "/ES");
+        assert_eq!(product.listed_months, vec!["H", "M", "U", "Z"]);
+        assert_eq!(product.tick_size, dec("0.25"));
+        assert_eq!(product.cash_settled, Some(true));
+
+        let option_product: FutureOptionProduct = serde_json::from_value(json!({
+            "root-symbol": "EW1",
+            "code": "EW1",
+            "exchange": "CME",
+            "product-type": "Physical",
+            "expiration-type": "Weekly",
+            "settlement-delay-days": 0,
+            "market-sector": "Equity Index",
+            "cash-settled": false,
+            "display-factor": "0.01",
+            "future-product": es_product()
+        }))
+        .unwrap();
+        assert_eq!(option_product.root_symbol, "EW1");
+        assert_eq!(option_product.expiration_type, Some(ExpirationType::Weekly));
+    }
+
+    fn expiration(future: &str, root: &str, date: &str, dte: u64) -> serde_json::Value {
+        json!({
+            "underlying-symbol": future,
+            "root-symbol": "/ES",
+            "option-root-symbol": root,
+            "option-contract-symbol": format!("{root}Z4"),
+            "expiration-date": date,
+            "days-to-expiration": dte,
+            "expiration-type": "Weekly",
+            "settlement-type": "PM",
+            "notional-value": "0.5",
+            "display-factor": "0.01",
+            "strike-factor": "1.0",
+            "tick-sizes": [{ "value": "0.05", "threshold": "10.0" }, { "value": "0.25" }],
+            "strikes": [{
+                "strike-price": "5000.0",
+                "call": format!("./ESZ4 {root}Z4 241220C5000"),
+                "call-streamer-symbol": format!("./{root}Z24C5000:XCME"),
+                "put": format!("./ESZ4 {root}Z4 241220P5000"),
+                "put-streamer-symbol": format!("./{root}Z24P5000:XCME")
+            }]
+        })
+    }
+
+    #[test]
+    fn test_nested_chain_groups_by_future() {
+        let json = json!({
+            "futures": [
+                {
+                    "symbol": "/ESZ4",
+                    "root-symbol": "/ES",
+                    "expiration-date": "2024-12-20",
+                    "days-to-expiration": 60,
+                    "active-month": true,
+                    "next-active-month": false
+                },
+                {
+                    "symbol": "/ESH5",
+                    "root-symbol": "/ES",
+                    "expiration-date": "2025-03-21",
+                    "days-to-expiration": 151,
+                    "active-month": false,
+                    "next-active-month": true
+                }
+            ],
+            "option-chains": [
+                {
+                    "underlying-symbol": "/ES",
+                    "root-symbol": "ES",
+                    "exercise-style": "American",
+                    "expirations": [
+                        expiration("/ESZ4", "ES", "2024-12-20", 60),
+                        expiration("/ESH5", "ES", "2025-03-21", 151)
+                    ]
+                },
+                {
+                    "underlying-symbol": "/ES",
+                    "root-symbol": "EW1",
+                    "exercise-style": "American",
+                    "expirations": [expiration("/ESZ4", "EW1", "2024-11-01", 11)]
+                }
+            ]
+        });
+
+        let chain: FuturesOptionChain = serde_json::from_value(json).unwrap();
+        assert_eq!(chain.active_future().unwrap().symbol.0, "/ESZ4");
+
+        let grouped = chain.expirations_by_future();
+        assert_eq!(grouped.len(), 2);
+        let z4 = chain.expirations_for("/ESZ4");
+        assert_eq!(z4.len(), 2);
+        assert_eq!(z4[0].option_root_symbol, "EW1");
+        assert_eq!(z4[0].expiration_type, ExpirationType::Weekly);
+        assert_eq!(z4[1].option_root_symbol, "ES");
+        assert_eq!(z4[1].notional_value, dec("0.5"));
+        assert_eq!(z4[1].strikes[0].strike_price, dec("5000"));
+        assert_eq!(z4[1].settlement_type, Some(SettlementType::PM));
+        assert!(chain.expirations_for("/CLZ4").is_empty());
+    }
+}
@@ -0,0 +1,503 @@
+//! Implied volatility from quoted option prices.
+//!
+//! The solver inverts the model that fits the exercise style, as
+//! [`price_option`](super::option_pricing::price_option) does: Black-Scholes-Merton
+//! (the same model as the platform's volatility figures) for European options
+//! and the binomial tree for American ones. It uses a bracketed Newton
+//! iteration, so it always reports whether it converged. Quotes can come from the streamer
+//! ([`QuoteData`]) or from [`MarketDataItem`]s; [`volatility_smiles`] solves
+//! a whole batch and groups the results by expiration.
+//!
+//! # Example
+//! ```ignore
+//! let option: OptionSymbol = "SPY   240216P00450000".parse()?;
+//! let inputs = PricingInputs::new(spot, Decimal::ZERO, today).rate(dec!(0.05));
+//! if let Some(iv) = implied_volatility_from_quote(&option, &quote, &inputs, ExerciseStyle::American) {
+//!     println!("{:?} {:?}", iv.volatility, iv.status);
+//! }
+//! let smiles = volatility_smiles(&market_data, &inputs, ExerciseStyle::American);
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+use super::instrument::{ExerciseStyle, OptionType};
+use super::market_data::MarketDataItem;
+use super::option_pricing::{Model, PricingInputs, BINOMIAL_STEPS};
+use super::option_symbol::OptionSymbol;
+use super::quote_streaming::QuoteData;
+
+/// Iteration limit for the solver
+const MAX_ITERATIONS: u32 = 100;
+
+/// Price error accepted as converged
+const PRICE_TOLERANCE: f64 = 1e-9;
+
+/// Search range for volatility, as annualized fractions
+const MIN_VOLATILITY: f64 = 1e-4;
+const MAX_VOLATILITY: f64 = 5.0;
+
+/// Outcome of an implied volatility solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveStatus {
+    Converged,
+    /// Stopped at the iteration limit; the volatility is the best estimate
+    MaxIterations,
+    /// The price is at or below the option's intrinsic value
+    BelowIntrinsic,
+    /// The price is at or above the most the option can be worth
+    AboveUpperBound,
+    /// No volatility in the search range reproduces the price
+    OutOfRange,
+    /// The option has no time left
+    Expired,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImpliedVolatility {
+    /// Annualized volatility (0.25 is 25%), or `None` when no volatility fits the price
+    pub volatility: Option<f64>,
+    pub status: SolveStatus,
+    pub iterations: u32,
+}
+
+impl ImpliedVolatility {
+    pub fn is_converged(&self) -> bool {
+        self.status == SolveStatus::Converged
+    }
+
+    fn unsolved(status: SolveStatus) -> Self {
+        Self {
+            volatility: None,
+            status,
+            iterations: 0,
+        }
+    }
+}
+
+/// Solve for the volatility at which `option` is worth `price`.
+///
+/// `inputs` supplies spot, rate, dividend yield and valuation date; a
+/// positive `inputs.volatility` is used as the starting guess. European
+/// options are solved with Black-Scholes, American and unknown styles with
+/// the binomial tree.
+pub fn implied_volatility(
+    option: &OptionSymbol,
+    price: Decimal,
+    inputs: &PricingInputs,
+    exercise_style: ExerciseStyle,
+) -> ImpliedVolatility {
+    let base = Model::new(option, inputs);
+    let target = price.to_f64().unwrap_or(0.0);
+    if base.time <= 0.0 {
+        return ImpliedVolatility::unsolved(SolveStatus::Expired);
+    }
+
+    let european = exercise_style == ExerciseStyle::European;
+    let model_price = |model: &Model| {
+        if european {
+            model.black_scholes_price()
+        } else {
+            model.american_price(BINOMIAL_STEPS)
+        }
+    };
+    let floor = model_price(&Model {
+        volatility: 0.0,
+        ..base
+    });
+    // Early exercise lifts the bounds to the undiscounted spot and strike
+    let ceiling = match (base.call, european) {
+        (true, true) => base.spot * (-base.dividend_yield * base.time).exp(),
+        (false, true) => base.strike * (-base.rate * base.time).exp(),
+        (true, false) => base.spot,
+        (false, false) => base.strike,
+    };
+    if target <= floor + PRICE_TOLERANCE {
+        return ImpliedVolatility::unsolved(SolveStatus::BelowIntrinsic);
+    }
+    if target >= ceiling {
+        return ImpliedVolatility::unsolved(SolveStatus::AboveUpperBound);
+    }
+
+    let price_at = |volatility: f64| Model { volatility, ..base };
+    let (mut low, mut high) = (MIN_VOLATILITY, MAX_VOLATILITY);
+    let mut volatility = if base.volatility > 0.0 {
+        base.volatility
+    } else {
+        // Brenner-Subrahmanyam approximation for at-the-money options
+        (2.0 * std::f64::consts::PI / base.time).sqrt() * target / base.spot
+    }
+    .clamp(low, high);
+
+    for iteration in 1..=MAX_ITERATIONS {
+        let model = price_at(volatility);
+        let error = model_price(&model) - target;
+        if error.abs() < PRICE_TOLERANCE {
+            return ImpliedVolatility {
+                volatility: Some(volatility),
+                status: SolveStatus::Converged,
+                iterations: iteration,
+            };
+        }
+        if high - low < 1e-12 {
+            // The bracket collapsed onto a bound without matching the price
+            return ImpliedVolatility {
+                iterations: iteration,
+                ..ImpliedVolatility::unsolved(SolveStatus::OutOfRange)
+            };
+        }
+        if error > 0.0 {
+            high = volatility;
+        } else {
+            low = volatility;
+        }
+
+        // Newton step, falling back to bisection when it leaves the bracket;
+        // the European vega is close enough to steer the American solve
+        let vega = model.black_scholes_vega();
+        let newton = volatility - error / vega;
+        volatility = if vega > 1e-12 && newton > low && newton < high {
+            newton
+        } else {
+            (low + high) / 2.0
+        };
+    }
+
+    ImpliedVolatility {
+        volatility: Some(volatility),
+        status: SolveStatus::MaxIterations,
+        iterations: MAX_ITERATIONS,
+    }
+}
+
+/// Solve for the volatility implied by a quote's price, if it has one.
+pub fn implied_volatility_from_quote(
+    option: &OptionSymbol,
+    quote: &impl OptionQuote,
+    inputs: &PricingInputs,
+    exercise_style: ExerciseStyle,
+) -> Option<ImpliedVolatility> {
+    Some(implied_volatility(
+        option,
+        quote.quoted_price()?,
+        inputs,
+        exercise_style,
+    ))
+}
+
+/// A quote that carries an option symbol and a price to solve against.
+pub trait OptionQuote {
+    /// The contract, parsed from the quote's OCC or streamer symbol.
+    fn option_symbol(&self) -> Option<OptionSymbol>;
+
+    /// The price to solve against, per share.
+    fn quoted_price(&self) -> Option<Decimal>;
+}
+
+impl OptionQuote for QuoteData {
+    fn option_symbol(&self) -> Option<OptionSymbol> {
+        self.symbol.parse().ok()
+    }
+
+    /// Mid of a two-sided market.
+    fn quoted_price(&self) -> Option<Decimal> {
+        match (self.bid_price, self.ask_price) {
+            (Some(bid), Some(ask)) if bid > 0.0 && ask >= bid => {
+                Decimal::from_f64((bid + ask) / 2.0)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl OptionQuote for MarketDataItem {
+    fn option_symbol(&self) -> Option<OptionSymbol> {
+        self.symbol.parse().ok()
+    }
+
+    /// The mid, then the mark, then the mid of bid and ask.
+    fn quoted_price(&self) -> Option<Decimal> {
+        self.mid
+            .or(self.mark)
+            .or_else(|| Some((self.bid? + self.ask?) / Decimal::TWO))
+            .filter(|price| price.is_sign_positive() && !price.is_zero())
+    }
+}
+
+/// One solved contract on a smile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmilePoint {
+    pub option: OptionSymbol,
+    pub price: Decimal,
+    pub implied_volatility: ImpliedVolatility,
+}
+
+/// Implied volatilities of one expiration, ordered by strike with calls first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolatilitySmile {
+    pub expiration: NaiveDate,
+    pub points: Vec<SmilePoint>,
+}
+
+impl VolatilitySmile {
+    /// Points whose solve converged.
+    pub fn converged(&self) -> impl Iterator<Item = &SmilePoint> {
+        self.points
+            .iter()
+            .filter(|p| p.implied_volatility.is_converged())
+    }
+
+    /// The converged volatility of one contract on the smile.
+    pub fn volatility_at(&self, strike: Decimal, option_type: OptionType) -> Option<f64> {
+        self.converged()
+            .find(|p| p.option.strike == strike && p.option.option_type == option_type)?
+            .implied_volatility
+            .volatility
+    }
+}
+
+/// Solve every option quote and group the results into one smile per expiration.
+///
+/// Quotes without a parseable option symbol or a price are skipped; unsolvable
+/// prices are kept with their status.
+pub fn volatility_smiles<'a, Q: OptionQuote + 'a>(
+    quotes: impl IntoIterator<Item = &'a Q>,
+    inputs: &PricingInputs,
+    exercise_style: ExerciseStyle,
+) -> Vec<VolatilitySmile> {
+    let mut smiles: BTreeMap<NaiveDate, Vec<SmilePoint>> = BTreeMap::new();
+    for quote in quotes {
+        let (Some(option), Some(price)) = (quote.option_symbol(), quote.quoted_price()) else {
+            continue;
+        };
+        let implied_volatility = implied_volatility(&option, price, inputs, exercise_style);
+        smiles
+            .entry(option.expiration)
+            .or_default()
+            .push(SmilePoint {
+                option,
+                price,
+                implied_volatility,
+            });
+    }
+    smiles
+        .into_iter()
+        .map(|(expiration, mut points)| {
+            points.sort_by(|a, b| {
+                a.option
+                    .strike
+                    .cmp(&b.option.strike)
+                    .then_with(|| a.option.is_call().cmp(&b.option.is_call()).reverse())
+            });
+            VolatilitySmile { expiration, points }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::option_pricing::{american, black_scholes};
+    use crate::api::test_util::{date, dec};
+    use serde_json::json;
+
+    fn inputs() -> PricingInputs {
+        PricingInputs::new(Decimal::from(100), Decimal::ZERO, date(2024, 1, 2)).rate(dec("0.05"))
+    }
+
+    fn option(option_type: OptionType, strike: i64) -> OptionSymbol {
+        OptionSymbol::new("XYZ", date(2024, 7, 1), option_type, Decimal::from(strike))
+    }
+
+    fn quote(symbol: &str, bid: Option<f64>, ask: Option<f64>) -> QuoteData {
+        QuoteData {
+            symbol: symbol.to_string(),
+            bid_price: bid,
+            ask_price: ask,
+            bid_size: None,
+            ask_size: None,
+            event_time: None,
+            day_volume: None,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for (option_type, strike, volatility) in [
+            (OptionType::Call, 100, "0.2"),
+            (OptionType::Put, 100, "0.35"),
+            (OptionType::Call, 130, "0.6"),
+            (OptionType::Put, 70, "0.9"),
+            (OptionType::Call, 80, "0.15"),
+        ] {
+            let option = option(option_type, strike);
+            let mut priced = inputs();
+            priced.volatility = dec(volatility);
+            let price = black_scholes(&option, &priced).price;
+
+            let solved = implied_volatility(&option, price, &inputs(), ExerciseStyle::European);
+            assert!(solved.is_converged(), "{option} {solved:?}");
+            let expected = dec(volatility).to_f64().unwrap();
+            assert!(
+                (solved.volatility.unwrap() - expected).abs() < 1e-5,
+                "{option}: {solved:?} vs {expected}"
+            );
+            assert!(solved.iterations <= 20);
+        }
+    }
+
+    #[test]
+    fn test_unsolvable_prices() {
+        let call = option(OptionType::Call, 90);
+        // Intrinsic (forward) value is above 10
+        assert_eq!(
+            implied_volatility(&call, dec("9.00"), &inputs(), ExerciseStyle::European).status,
+            SolveStatus::BelowIntrinsic
+        );
+        assert_eq!(
+            implied_volatility(&call, dec("101"), &inputs(), ExerciseStyle::European).status,
+            SolveStatus::AboveUpperBound
+        );
+        let expired = implied_volatility(
+            &call,
+            dec("10"),
+            &inputs().on(date(2024, 7, 1)),
+            ExerciseStyle::European,
+        );
+        assert_eq!(expired.status, SolveStatus::Expired);
+        assert!(expired.volatility.is_none());
+
+        // Worth more than even the highest volatility in the search range
+        let mut priced = inputs();
+        priced.volatility = dec("8");
+        let price = black_scholes(&call, &priced).price;
+        let out_of_range = implied_volatility(&call, price, &inputs(), ExerciseStyle::European);
+        assert_eq!(out_of_range.status, SolveStatus::OutOfRange);
+        assert!(out_of_range.volatility.is_none());
+    }
+
+    #[test]
+    fn test_american_round_trip() {
+        for (option_type, strike, volatility) in [
+            (OptionType::Put, 110, "0.25"),
+            (OptionType::Put, 100, "0.4"),
+            (OptionType::Call, 100, "0.3"),
+        ] {
+            let option = option(option_type, strike);
+            let mut priced = inputs();
+            priced.volatility = dec(volatility);
+            let price = american(&option, &priced).price;
+
+            let solved = implied_volatility(&option, price, &inputs(), ExerciseStyle::American);
+            assert!(solved.is_converged(), "{option} {solved:?}");
+            let expected = dec(volatility).to_f64().unwrap();
+            assert!(
+                (solved.volatility.unwrap() - expected).abs() < 1e-5,
+                "{option}: {solved:?} vs {expected}"
+            );
+        }
+
+        // The early exercise premium reads as extra volatility under Black-Scholes
+        let put = option(OptionType::Put, 110);
+        let mut priced = inputs();
+        priced.volatility = dec("0.25");
+        let price = american(&put, &priced).price;
+        let european = implied_volatility(&put, price, &inputs(), ExerciseStyle::European);
+        assert!(european.volatility.unwrap() > 0.25 + 1e-3);
+    }
+
+    #[test]
+    fn test_quoted_prices() {
+        let streamed = quote(".XYZ240701C100", Some(6.1), Some(6.3));
+        assert_eq!(streamed.quoted_price(), Some(dec("6.2")));
+        assert_eq!(
+            streamed.option_symbol(),
+            Some(option(OptionType::Call, 100))
+        );
+        assert!(quote(".XYZ240701C100", None, Some(6.3))
+            .quoted_price()
+            .is_none());
+        assert!(quote(".XYZ240701C100", Some(0.0), Some(0.05))
+            .quoted_price()
+            .is_none());
+
+        let items: Vec<MarketDataItem> = serde_json::from_value(json!([
+            { "symbol": "XYZ   240701P00100000", "instrument-type": "Equity Option", "mid": "3.4", "mark": "3.5" },
+            { "symbol": "XYZ   240701P00095000", "instrument-type": "Equity Option", "mark": "1.9" },
+            { "symbol": "XYZ   240701P00090000", "instrument-type": "Equity Option", "bid": "0.9", "ask": "1.1" },
+            { "symbol": "XYZ   240701P00085000", "instrument-type": "Equity Option" }
+        ]))
+        .unwrap();
+        let prices: Vec<_> = items.iter().map(|i| i.quoted_price()).collect();
+        assert_eq!(
+            prices,
+            vec![Some(dec("3.4")), Some(dec("1.9")), Some(dec("1.0")), None]
+        );
+        assert_eq!(items[1].option_symbol(), Some(option(OptionType::Put, 95)));
+
+        let solved = implied_volatility_from_quote(
+            &option(OptionType::Call, 100),
+            &streamed,
+            &inputs(),
+            ExerciseStyle::European,
+        )
+        .unwrap();
+        assert!(solved.is_converged());
+    }
+
+    #[test]
+    fn test_volatility_smiles() {
+        // A skewed smile: lower strikes carry more volatility
+        let mut quotes = Vec::new();
+        for (expiration, symbol_date) in
+            [(date(2024, 7, 1), "240701"), (date(2024, 3, 15), "240315")]
+        {
+            for (strike, volatility) in [(110, "0.18"), (90, "0.3"), (100, "0.22")] {
+                for option_type in [OptionType::Put, OptionType::Call] {
+                    let contract =
+                        OptionSymbol::new("XYZ", expiration, option_type, Decimal::from(strike));
+                    let mut priced = inputs();
+                    priced.volatility = dec(volatility);
+                    let price = black_scholes(&contract, &priced).price.to_f64().unwrap();
+                    let code = if option_type == OptionType::Call {
+                        "C"
+                    } else {
+                        "P"
+                    };
+                    quotes.push(quote(
+                        &format!(".XYZ{symbol_date}{code}{strike}"),
+                        Some(price - 0.01),
+                        Some(price + 0.01),
+                    ));
+                }
+            }
+        }
+        quotes.push(quote("XYZ", Some(99.9), Some(100.1)));
+
+        let smiles = volatility_smiles(&quotes, &inputs(), ExerciseStyle::European);
+        assert_eq!(smiles.len(), 2);
+        assert_eq!(smiles[0].expiration, date(2024, 3, 15));
+
+        let smile = &smiles[1];
+        assert_eq!(smile.points.len(), 6);
+        assert_eq!(smile.converged().count(), 6);
+        assert_eq!(smile.points[0].option.strike, Decimal::from(90));
+        assert!(smile.points[0].option.is_call());
+        assert!(!smile.points[1].option.is_call());
+
+        let put_90 = smile
+            .volatility_at(Decimal::from(90), OptionType::Put)
+            .unwrap();
+        let call_110 = smile
+            .volatility_at(Decimal::from(110), OptionType::Call)
+            .unwrap();
+        assert!((put_90 - 0.3).abs() < 1e-3);
+        assert!((call_110 - 0.18).abs() < 1e-3);
+        assert!(smile
+            .volatility_at(Decimal::from(105), OptionType::Call)
+            .is_none());
+    }
+}
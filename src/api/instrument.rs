@@ -1,12 +1,26 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::stream::{self, StreamExt};
+use rust_decimal::Decimal;
 use serde::Deserialize;
 
+use crate::api::base::{Items, TastyError};
 use crate::Result;
 use crate::TastyTrade;
 
 use super::order::AsSymbol;
+use super::order::InstrumentType;
 use super::order::Symbol;
 use super::quote_streaming::DxFeedSymbol;
 
+/// Symbols sent per `/instruments/equity-options` request
+pub const EQUITY_OPTIONS_CHUNK_SIZE: usize = 100;
+
+/// Maximum number of instrument lookups in flight at once in
+/// [`TastyTrade::streamer_symbols_for`]
+pub const STREAMER_SYMBOL_CONCURRENCY: usize = 4;
+
 impl TastyTrade {
     /// Get equity instrument info for a single symbol.
     ///
@@ -43,6 +57,237 @@ impl TastyTrade {
 
         self.get(format!("/instruments/equities?{}", query)).await
     }
+
+    /// Get the full equity option instrument for a single OCC symbol.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let option = client.get_equity_option("AAPL  240119C00150000").await?;
+    /// println!("{} {:?} expires {}", option.strike_price, option.option_type, option.expiration_date);
+    /// ```
+    pub async fn get_equity_option(&self, symbol: impl AsSymbol) -> Result<EquityOption> {
+        self.get(format!(
+            "/instruments/equity-options/{}",
+            encode_path_segment(&symbol.as_symbol().0)
+        ))
+        .await
+    }
+
+    /// Get equity option instruments for multiple OCC symbols, splitting large
+    /// lists into requests of [`EQUITY_OPTIONS_CHUNK_SIZE`] symbols.
+    pub async fn get_equity_options(&self, symbols: &[impl AsSymbol]) -> Result<Vec<EquityOption>> {
+        let mut options = Vec::with_capacity(symbols.len());
+        for chunk in symbol_chunks(symbols) {
+            let query: Vec<(&str, &str)> =
+                chunk.iter().map(|s| ("symbol[]", s.0.as_str())).collect();
+            let resp: Items<EquityOption> = self
+                .get_with_query("/instruments/equity-options", &query)
+                .await?;
+            options.extend(resp.items);
+        }
+        Ok(options)
+    }
+
+    /// Search for symbols starting with `query`, e.g. for autocomplete.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for hit in client.search_symbols("AAP").await? {
+    ///     println!("{} - {}", hit.symbol.0, hit.description.unwrap_or_default());
+    /// }
+    /// ```
+    pub async fn search_symbols(&self, query: &str) -> Result<Vec<SymbolSearchResult>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+        let resp: Items<SymbolSearchResult> = self
+            .get(format!("/symbols/search/{}", encode_path_segment(query)))
+            .await?;
+        Ok(resp.items)
+    }
+
+    /// Search for symbols starting with `query`, keeping only one instrument type.
+    pub async fn search_symbols_of_type(
+        &self,
+        query: &str,
+        instrument_type: &InstrumentType,
+    ) -> Result<Vec<SymbolSearchResult>> {
+        let mut results = self.search_symbols(query).await?;
+        results.retain(|r| r.instrument_type.as_ref() == Some(instrument_type));
+        Ok(results)
+    }
+
+    /// Look up the tradability flags of any instrument type with its own
+    /// instruments endpoint.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let es = client
+    ///     .get_instrument_summary(&InstrumentType::Future, "/ESZ4")
+    ///     .await?;
+    /// assert_eq!(es.active, Some(true));
+    /// ```
+    pub async fn get_instrument_summary(
+        &self,
+        instrument_type: &InstrumentType,
+        symbol: impl AsSymbol,
+    ) -> Result<InstrumentSummary> {
+        let symbol = symbol.as_symbol();
+        let path = instrument_path(instrument_type, &symbol).ok_or_else(|| {
+            TastyError::Config(format!(
+                "no instruments endpoint for {} symbols",
+                instrument_type.as_api_str()
+            ))
+        })?;
+        self.get(path).await
+    }
+
+    /// Resolve the DxLink streamer symbol for an instrument of any type with
+    /// an instruments endpoint.
+    ///
+    /// Streamer symbols differ from trading symbols for options, futures and
+    /// crypto (`BTC/USD` streams as `BTC/USD:CXTALP`), so subscribe with the
+    /// result rather than the trading symbol.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let btc = client
+    ///     .streamer_symbol_for(&InstrumentType::Cryptocurrency, "BTC/USD")
+    ///     .await?;
+    /// streamer.subscribe_quotes(channel_id, &[btc.0]).await?;
+    /// ```
+    pub async fn streamer_symbol_for(
+        &self,
+        instrument_type: &InstrumentType,
+        symbol: impl AsSymbol,
+    ) -> Result<DxFeedSymbol> {
+        let symbol = symbol.as_symbol();
+        let path = instrument_path(instrument_type, &symbol).ok_or_else(|| {
+            TastyError::Config(format!(
+                "no streamer symbol lookup for {} symbols",
+                instrument_type.as_api_str()
+            ))
+        })?;
+        let resp: StreamerSymbolOnly = self.get(path).await?;
+        Ok(resp.streamer_symbol)
+    }
+
+    /// Resolve streamer symbols for many instruments, e.g. every open position.
+    ///
+    /// Lookups run concurrently, at most [`STREAMER_SYMBOL_CONCURRENCY`] at a
+    /// time. Returns a map from trading symbol to its streamer symbol, or to the
+    /// error its lookup hit, so one bad symbol never hides the rest.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let resolved = client.streamer_symbols_for(&instruments).await;
+    /// let streamer_symbols: Vec<String> = resolved
+    ///     .iter()
+    ///     .filter_map(|(symbol, result)| match result {
+    ///         Ok(streamer_symbol) => Some(streamer_symbol.0.clone()),
+    ///         Err(e) => {
+    ///             eprintln!("cannot stream {}: {}", symbol.0, e);
+    ///             None
+    ///         }
+    ///     })
+    ///     .collect();
+    /// ```
+    pub async fn streamer_symbols_for(
+        &self,
+        instruments: &[(InstrumentType, Symbol)],
+    ) -> HashMap<Symbol, Result<DxFeedSymbol>> {
+        let mut seen = HashSet::with_capacity(instruments.len());
+        let unique: Vec<&(InstrumentType, Symbol)> = instruments
+            .iter()
+            .filter(|(_, symbol)| seen.insert(symbol))
+            .collect();
+        stream::iter(unique)
+            .map(|(instrument_type, symbol)| async move {
+                let result = self.streamer_symbol_for(instrument_type, symbol).await;
+                (symbol.clone(), result)
+            })
+            .buffer_unordered(STREAMER_SYMBOL_CONCURRENCY)
+            .collect()
+            .await
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct StreamerSymbolOnly {
+    streamer_symbol: DxFeedSymbol,
+}
+
+/// `symbols` split into one list per request.
+fn symbol_chunks(symbols: &[impl AsSymbol]) -> Vec<Vec<Symbol>> {
+    symbols
+        .chunks(EQUITY_OPTIONS_CHUNK_SIZE)
+        .map(|chunk| chunk.iter().map(AsSymbol::as_symbol).collect())
+        .collect()
+}
+
+/// Instruments endpoint path for a single symbol of the given type.
+pub(crate) fn instrument_path(instrument_type: &InstrumentType, symbol: &Symbol) -> Option<String> {
+    let (collection, symbol) = match instrument_type {
+        InstrumentType::Equity => ("equities", symbol.0.as_str()),
+        InstrumentType::EquityOption => ("equity-options", symbol.0.as_str()),
+        // The futures endpoint takes the contract code without its leading slash
+        InstrumentType::Future => ("futures", symbol.0.trim_start_matches('/')),
+        InstrumentType::FutureOption => ("future-options", symbol.0.as_str()),
+        InstrumentType::Cryptocurrency => ("cryptocurrencies", symbol.0.as_str()),
+        _ => return None,
+    };
+    Some(format!(
+        "/instruments/{}/{}",
+        collection,
+        encode_path_segment(symbol)
+    ))
+}
+
+/// Percent-encode a symbol for use as a single URL path segment, so option
+/// padding and the slash in `BTC/USD` survive.
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// A single match from `/symbols/search`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SymbolSearchResult {
+    pub symbol: Symbol,
+
+    /// Company or instrument name (e.g., "Apple Inc. - Common Stock")
+    pub description: Option<String>,
+
+    pub instrument_type: Option<InstrumentType>,
+
+    /// Listed market/exchange (e.g., "XNAS")
+    pub listed_market: Option<String>,
+
+    /// Whether options are listed on the symbol
+    pub options: Option<bool>,
+}
+
+/// Tradability flags common to every instrument type.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InstrumentSummary {
+    pub symbol: Symbol,
+    pub instrument_type: Option<String>,
+    /// Whether the instrument is currently active/tradeable
+    pub active: Option<bool>,
+    /// Whether only closing orders are accepted
+    pub is_closing_only: Option<bool>,
 }
 
 /// Tick size threshold for equity trading.
@@ -123,6 +368,117 @@ pub struct EquityInstrumentInfo {
     pub option_tick_sizes: Option<Vec<TickSize>>,
 }
 
+/// Call or put, as reported by the instruments API (`C` / `P`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum OptionType {
+    #[serde(rename = "C", alias = "Call")]
+    Call,
+    #[serde(rename = "P", alias = "Put")]
+    Put,
+}
+
+/// When an option can be exercised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ExerciseStyle {
+    American,
+    European,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Whether an option settles on the opening (AM) or closing (PM) price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum SettlementType {
+    AM,
+    PM,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Listing cycle of an option expiration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum ExpirationType {
+    Regular,
+    Weekly,
+    Quarterly,
+    #[serde(rename = "End-Of-Month")]
+    EndOfMonth,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Whether an option chain carries the standard deliverable or an adjusted one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum OptionChainType {
+    Standard,
+    #[serde(rename = "Non-standard")]
+    NonStandard,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Complete equity option instrument from `/instruments/equity-options`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EquityOption {
+    /// OCC symbol (e.g., "AAPL  240119C00150000")
+    pub symbol: Symbol,
+
+    /// Symbol of the underlying equity or index (e.g., "AAPL")
+    pub underlying_symbol: Symbol,
+
+    /// Option root, which differs from the underlying for weeklies and adjusted
+    /// options (e.g., "SPXW")
+    pub root_symbol: Symbol,
+
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub strike_price: Decimal,
+
+    pub expiration_date: NaiveDate,
+
+    pub option_type: OptionType,
+
+    pub exercise_style: Option<ExerciseStyle>,
+
+    pub settlement_type: Option<SettlementType>,
+
+    pub expiration_type: Option<ExpirationType>,
+
+    pub days_to_expiration: Option<u64>,
+
+    /// Deliverable shares per contract, usually 100
+    pub shares_per_contract: Option<u64>,
+
+    /// Non-standard for adjusted deliverables
+    pub option_chain_type: Option<OptionChainType>,
+
+    /// Whether the option is currently active/tradeable
+    pub active: Option<bool>,
+
+    /// Whether only closing orders are accepted
+    pub is_closing_only: Option<bool>,
+
+    /// Exact expiration time
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Time trading stops on the expiration date
+    pub stops_trading_at: Option<DateTime<Utc>>,
+
+    /// Symbol used for DxLink streaming subscriptions
+    pub streamer_symbol: DxFeedSymbol,
+}
+
+impl EquityOption {
+    pub fn is_call(&self) -> bool {
+        self.option_type == OptionType::Call
+    }
+
+    /// Contract multiplier, defaulting to 100 when the API omits it.
+    pub fn multiplier(&self) -> Decimal {
+        Decimal::from(self.shares_per_contract.unwrap_or(100))
+    }
+}
+
 impl EquityInstrumentInfo {
     /// Returns the company/security name, preferring short_description.
     ///
@@ -297,6 +653,143 @@ mod tests {
         assert_eq!(info3.name(), "XYZ");
     }
 
+    #[test]
+    fn test_equity_option_deserialization() {
+        let json = json!({
+            "active": true,
+            "days-to-expiration": 30,
+            "exercise-style": "American",
+            "expiration-date": "2024-01-19",
+            "expiration-type": "Regular",
+            "expires-at": "2024-01-19T21:00:00.000+00:00",
+            "instrument-type": "Equity Option",
+            "is-closing-only": false,
+            "listed-market": "OCC",
+            "option-chain-type": "Standard",
+            "option-type": "C",
+            "root-symbol": "AAPL",
+            "settlement-type": "PM",
+            "shares-per-contract": 100,
+            "stops-trading-at": "2024-01-19T21:00:00.000+00:00",
+            "streamer-symbol": ".AAPL240119C150",
+            "strike-price": "150.0",
+            "symbol": "AAPL  240119C00150000",
+            "underlying-symbol": "AAPL"
+        });
+
+        let option: EquityOption = serde_json::from_value(json).unwrap();
+        assert_eq!(option.underlying_symbol.0, "AAPL");
+        assert_eq!(option.strike_price, Decimal::from(150));
+        assert_eq!(
+            option.expiration_date,
+            NaiveDate::from_ymd_opt(2024, 1, 19).unwrap()
+        );
+        assert!(option.is_call());
+        assert_eq!(option.exercise_style, Some(ExerciseStyle::American));
+        assert_eq!(option.settlement_type, Some(SettlementType::PM));
+        assert_eq!(option.expiration_type, Some(ExpirationType::Regular));
+        assert_eq!(option.option_chain_type, Some(OptionChainType::Standard));
+        assert_eq!(option.days_to_expiration, Some(30));
+        assert_eq!(option.multiplier(), Decimal::from(100));
+        assert_eq!(option.is_closing_only, Some(false));
+        assert!(option.expires_at.is_some());
+        assert_eq!(option.streamer_symbol.0, ".AAPL240119C150");
+    }
+
+    #[test]
+    fn test_equity_option_minimal_and_batch() {
+        let json = json!({
+            "items": [
+                {
+                    "symbol": "SPXW  240119P04500000",
+                    "underlying-symbol": "SPX",
+                    "root-symbol": "SPXW",
+                    "strike-price": "4500.0",
+                    "expiration-date": "2024-01-19",
+                    "option-type": "P",
+                    "exercise-style": "European",
+                    "settlement-type": "Something New",
+                    "streamer-symbol": ".SPXW240119P4500"
+                }
+            ]
+        });
+
+        let items: Items<EquityOption> = serde_json::from_value(json).unwrap();
+        let option = &items.items[0];
+        assert_eq!(option.option_type, OptionType::Put);
+        assert_eq!(option.root_symbol.0, "SPXW");
+        assert_eq!(option.exercise_style, Some(ExerciseStyle::European));
+        assert_eq!(option.settlement_type, Some(SettlementType::Unknown));
+        assert!(option.active.is_none());
+    }
+
+    #[test]
+    fn test_symbol_search_result_deserialization() {
+        let json = json!({
+            "items": [
+                {
+                    "symbol": "AAPL",
+                    "description": "Apple Inc. - Common Stock",
+                    "listed-market": "XNAS",
+                    "price-increments": "0.01",
+                    "trading-hours": "",
+                    "options": true,
+                    "instrument-type": "Equity"
+                },
+                {
+                    "symbol": "AAPB",
+                    "description": "GraniteShares 2x Long AAPL Daily ETF",
+                    "options": false,
+                    "instrument-type": "Something Else"
+                },
+                {
+                    "symbol": "AAPX"
+                }
+            ]
+        });
+
+        let items: Items<SymbolSearchResult> = serde_json::from_value(json).unwrap();
+        assert_eq!(items.items.len(), 3);
+        assert_eq!(items.items[0].symbol.0, "AAPL");
+        assert_eq!(items.items[0].instrument_type, Some(InstrumentType::Equity));
+        assert_eq!(items.items[0].listed_market.as_deref(), Some("XNAS"));
+        assert_eq!(items.items[0].options, Some(true));
+        assert_eq!(
+            items.items[1].instrument_type,
+            Some(InstrumentType::Unknown)
+        );
+        assert!(items.items[2].description.is_none());
+    }
+
+    #[test]
+    fn test_instrument_path_encodes_symbols() {
+        assert_eq!(
+            instrument_path(&InstrumentType::Future, &Symbol::from("/ESZ4")).unwrap(),
+            "/instruments/futures/ESZ4"
+        );
+        assert_eq!(
+            instrument_path(&InstrumentType::Cryptocurrency, &Symbol::from("BTC/USD")).unwrap(),
+            "/instruments/cryptocurrencies/BTC%2FUSD"
+        );
+        assert_eq!(
+            instrument_path(
+                &InstrumentType::EquityOption,
+                &Symbol::from("SPY   240119P00450000")
+            )
+            .unwrap(),
+            "/instruments/equity-options/SPY%20%20%20240119P00450000"
+        );
+        assert_eq!(
+            instrument_path(
+                &InstrumentType::FutureOption,
+                &Symbol::from("./ESZ4 EW4U4 240920C5000")
+            )
+            .unwrap(),
+            "/instruments/future-options/.%2FESZ4%20EW4U4%20240920C5000"
+        );
+        assert!(instrument_path(&InstrumentType::Bond, &Symbol::from("X")).is_none());
+    }
+
     #[test]
     fn test_tick_size_deserialization() {
         let json = json!({
@@ -317,4 +810,18 @@ mod tests {
         assert_eq!(tick2.value, "0.05");
         assert_eq!(tick2.threshold, None);
     }
+
+    #[test]
+    fn test_symbol_chunks() {
+        let symbols: Vec<String> = (0..250).map(|i| format!("S{i}")).collect();
+        let chunks = symbol_chunks(&symbols);
+        let sizes: Vec<usize> = chunks.iter().map(Vec::len).collect();
+        assert_eq!(
+            sizes,
+            vec![EQUITY_OPTIONS_CHUNK_SIZE, EQUITY_OPTIONS_CHUNK_SIZE, 50]
+        );
+        assert_eq!(chunks[2][49], Symbol::from("S249"));
+
+        assert!(symbol_chunks(&[] as &[&str]).is_empty());
+    }
 }
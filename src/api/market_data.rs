@@ -1,14 +1,21 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
+use futures_util::stream::{self, StreamExt};
 use rust_decimal::Decimal;
 use serde::de::Error as DeError;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde_json::Value;
 
-use crate::api::base::Result;
+use crate::api::base::{Result, TastyError};
 use crate::TastyTrade;
 
+/// Maximum symbols per `/market-data/by-type` request
+pub const MARKET_DATA_CHUNK_SIZE: usize = 100;
+
+/// Maximum number of market data requests in flight at once
+pub const MARKET_DATA_CONCURRENCY: usize = 4;
+
 /// Supported instrument groupings for the market data endpoint.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum MarketDataParam {
@@ -79,6 +86,11 @@ impl MarketDataRequest {
         self.add_symbol(MarketDataParam::EquityOption, symbol);
     }
 
+    /// Add a crypto pair by trading symbol (e.g., "BTC/USD").
+    pub fn add_crypto(&mut self, symbol: impl Into<String>) {
+        self.add_symbol(MarketDataParam::Cryptocurrency, symbol);
+    }
+
     pub fn with_equity(self, symbol: impl Into<String>) -> Self {
         self.with_symbol(MarketDataParam::Equity, symbol)
     }
@@ -87,10 +99,41 @@ impl MarketDataRequest {
         self.with_symbol(MarketDataParam::EquityOption, symbol)
     }
 
+    pub fn with_crypto(self, symbol: impl Into<String>) -> Self {
+        self.with_symbol(MarketDataParam::Cryptocurrency, symbol)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.params.values().all(BTreeSet::is_empty)
     }
 
+    /// Number of symbols across all instrument types.
+    pub fn len(&self) -> usize {
+        self.params.values().map(BTreeSet::len).sum()
+    }
+
+    /// Every requested symbol with its instrument type.
+    pub fn symbols(&self) -> impl Iterator<Item = (MarketDataParam, &str)> {
+        self.params
+            .iter()
+            .flat_map(|(param, symbols)| symbols.iter().map(move |s| (*param, s.as_str())))
+    }
+
+    /// Split into requests of a single instrument type with at most `size` symbols each.
+    pub fn chunks(&self, size: usize) -> Vec<MarketDataRequest> {
+        let size = size.max(1);
+        let mut chunks = Vec::new();
+        for (param, symbols) in &self.params {
+            let symbols: Vec<&String> = symbols.iter().collect();
+            for chunk in symbols.chunks(size) {
+                chunks.push(
+                    MarketDataRequest::new().with_symbols(*param, chunk.iter().copied().cloned()),
+                );
+            }
+        }
+        chunks
+    }
+
     pub fn to_query_pairs(&self) -> Vec<(String, String)> {
         self.params
             .iter()
@@ -179,10 +222,144 @@ pub struct MarketDataItem {
     pub extra: HashMap<String, Value>,
 }
 
+/// Symbols whose market data could not be fetched.
+#[derive(Debug)]
+pub struct MarketDataFailure {
+    pub param: MarketDataParam,
+    /// Usually a single symbol; a whole chunk when the request failed outright
+    pub symbols: Vec<String>,
+    pub error: TastyError,
+}
+
+/// Merged results of a chunked market data fetch.
+#[derive(Debug, Default)]
+pub struct MarketDataReport {
+    pub items: Vec<MarketDataItem>,
+    pub failures: Vec<MarketDataFailure>,
+    /// Symbols that were fetched without error but came back without an item
+    pub missing: Vec<(MarketDataParam, String)>,
+}
+
+impl MarketDataReport {
+    /// True if no request failed. Missing symbols do not count as failures.
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Every symbol that failed, with its instrument type.
+    pub fn failed_symbols(&self) -> impl Iterator<Item = (MarketDataParam, &str)> {
+        self.failures
+            .iter()
+            .flat_map(|f| f.symbols.iter().map(move |s| (f.param, s.as_str())))
+    }
+
+    /// The items, or the first error if any request failed.
+    ///
+    /// On failure the items that were fetched are discarded; read
+    /// [`items`](Self::items) and [`failures`](Self::failures) directly to keep
+    /// partial results.
+    pub fn into_result(self) -> Result<Vec<MarketDataItem>> {
+        match self.failures.into_iter().next() {
+            Some(failure) => Err(failure.error),
+            None => Ok(self.items),
+        }
+    }
+
+    fn fill_missing(&mut self, request: &MarketDataRequest) {
+        let returned: HashSet<&str> = self.items.iter().map(|i| i.symbol.as_str()).collect();
+        let failed: HashSet<(MarketDataParam, &str)> = self.failed_symbols().collect();
+        self.missing = request
+            .symbols()
+            .filter(|(param, symbol)| {
+                !returned.contains(symbol) && !failed.contains(&(*param, *symbol))
+            })
+            .map(|(param, symbol)| (param, symbol.to_string()))
+            .collect();
+    }
+}
+
+/// Whether a failed chunk is worth retrying symbol by symbol to isolate bad symbols.
+///
+/// Only rejections of the request itself qualify; auth, rate-limit and server
+/// errors would fail every single-symbol retry the same way.
+fn is_symbol_error(error: &TastyError) -> bool {
+    matches!(error.status(), Some(400 | 404 | 422)) || error.is_not_found()
+}
+
 impl TastyTrade {
+    /// Fetch market data for every symbol in `request`.
+    ///
+    /// Fails with the first error if any symbol could not be fetched; use
+    /// [`fetch_market_data_report`](Self::fetch_market_data_report) to keep
+    /// the symbols that succeeded.
     pub async fn fetch_market_data(
         &self,
         request: &MarketDataRequest,
+    ) -> Result<Vec<MarketDataItem>> {
+        self.fetch_market_data_report(request).await.into_result()
+    }
+
+    /// Fetch market data for every symbol in `request`, reporting failures
+    /// per symbol instead of failing the whole fetch.
+    ///
+    /// The request is split into chunks of at most [`MARKET_DATA_CHUNK_SIZE`]
+    /// symbols per instrument type, fetched at most
+    /// [`MARKET_DATA_CONCURRENCY`] at a time. When the API rejects a chunk's
+    /// symbols (400, 404 or 422), they are retried one by one so a single bad
+    /// symbol does not sink the rest; other errors, such as an expired token
+    /// or a rate limit, are reported once for the whole chunk. Whatever still
+    /// fails is listed in [`MarketDataReport::failures`].
+    ///
+    /// # Example
+    /// ```ignore
+    /// let report = client.fetch_market_data_report(&request).await;
+    /// for (param, symbol) in report.failed_symbols() {
+    ///     eprintln!("no data for {symbol} ({param:?})");
+    /// }
+    /// let items = report.items;
+    /// ```
+    pub async fn fetch_market_data_report(&self, request: &MarketDataRequest) -> MarketDataReport {
+        let mut report = MarketDataReport::default();
+        let mut retries = Vec::new();
+        for (chunk, result) in self
+            .fetch_market_data_chunks(request.chunks(MARKET_DATA_CHUNK_SIZE))
+            .await
+        {
+            match result {
+                Ok(items) => report.items.extend(items),
+                Err(error) if chunk.len() > 1 && is_symbol_error(&error) => {
+                    retries.extend(chunk.chunks(1));
+                }
+                Err(error) => report.failures.push(MarketDataFailure::new(&chunk, error)),
+            }
+        }
+        for (chunk, result) in self.fetch_market_data_chunks(retries).await {
+            match result {
+                Ok(items) => report.items.extend(items),
+                Err(error) => report.failures.push(MarketDataFailure::new(&chunk, error)),
+            }
+        }
+        report.fill_missing(request);
+        report
+    }
+
+    async fn fetch_market_data_chunks(
+        &self,
+        chunks: Vec<MarketDataRequest>,
+    ) -> Vec<(MarketDataRequest, Result<Vec<MarketDataItem>>)> {
+        stream::iter(chunks)
+            .map(|chunk| async move {
+                let result = self.fetch_market_data_chunk(&chunk).await;
+                (chunk, result)
+            })
+            .buffered(MARKET_DATA_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    async fn fetch_market_data_chunk(
+        &self,
+        request: &MarketDataRequest,
     ) -> Result<Vec<MarketDataItem>> {
         let query_pairs = request.to_query_pairs();
         let query_refs: Vec<(&str, &str)> = query_pairs
@@ -198,6 +375,21 @@ impl TastyTrade {
     }
 }
 
+impl MarketDataFailure {
+    fn new(chunk: &MarketDataRequest, error: TastyError) -> Self {
+        let mut symbols = chunk.symbols().peekable();
+        let param = symbols
+            .peek()
+            .map(|(param, _)| *param)
+            .unwrap_or(MarketDataParam::Equity);
+        Self {
+            param,
+            symbols: symbols.map(|(_, s)| s.to_string()).collect(),
+            error,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum MarketDataPayload {
@@ -247,6 +439,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::base::ApiError;
     use serde_json::{json, Value};
     use std::str::FromStr;
 
@@ -254,6 +447,95 @@ mod tests {
         Decimal::from_str(value).expect("invalid decimal literal")
     }
 
+    fn item(symbol: &str) -> MarketDataItem {
+        serde_json::from_value(json!({ "symbol": symbol, "instrument-type": "Equity" })).unwrap()
+    }
+
+    #[test]
+    fn request_chunks_split_by_param_and_size() {
+        let request = MarketDataRequest::new()
+            .with_symbols(
+                MarketDataParam::Equity,
+                ["AAPL", "MSFT", "SPY", "QQQ", "IWM"],
+            )
+            .with_equity_option("AAPL  250119C00150000");
+        assert_eq!(request.len(), 6);
+
+        let chunks = request.chunks(2);
+        let sizes: Vec<usize> = chunks.iter().map(MarketDataRequest::len).collect();
+        assert_eq!(sizes, vec![2, 2, 1, 1]);
+        for chunk in &chunks {
+            assert_eq!(chunk.to_query_pairs().len(), 1);
+        }
+        let rejoined: BTreeSet<&str> = chunks
+            .iter()
+            .flat_map(|c| c.symbols().map(|(_, s)| s))
+            .collect();
+        assert_eq!(rejoined.len(), 6);
+        assert!(MarketDataRequest::new()
+            .chunks(MARKET_DATA_CHUNK_SIZE)
+            .is_empty());
+    }
+
+    #[test]
+    fn report_tracks_failures_and_missing_symbols() {
+        let request =
+            MarketDataRequest::new().with_symbols(MarketDataParam::Equity, ["AAPL", "BAD", "GONE"]);
+        let mut report = MarketDataReport {
+            items: vec![item("AAPL")],
+            failures: vec![MarketDataFailure::new(
+                &MarketDataRequest::new().with_equity("BAD"),
+                TastyError::UnexpectedResponse {
+                    status: 400,
+                    body: "invalid symbol".to_string(),
+                },
+            )],
+            missing: Vec::new(),
+        };
+        report.fill_missing(&request);
+
+        assert!(!report.is_success());
+        assert_eq!(
+            report.failed_symbols().collect::<Vec<_>>(),
+            vec![(MarketDataParam::Equity, "BAD")]
+        );
+        assert_eq!(
+            report.missing,
+            vec![(MarketDataParam::Equity, "GONE".to_string())]
+        );
+        assert!(matches!(
+            report.into_result(),
+            Err(TastyError::UnexpectedResponse { status: 400, .. })
+        ));
+
+        let ok = MarketDataReport {
+            items: vec![item("AAPL")],
+            ..Default::default()
+        };
+        assert_eq!(ok.into_result().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn only_symbol_rejections_are_retried_per_symbol() {
+        let response = |status| TastyError::UnexpectedResponse {
+            status,
+            body: String::new(),
+        };
+        for status in [400, 404, 422] {
+            assert!(is_symbol_error(&response(status)), "{status}");
+        }
+        for status in [401, 403, 429, 500, 503] {
+            assert!(!is_symbol_error(&response(status)), "{status}");
+        }
+        let not_found = TastyError::Api(ApiError {
+            code: Some("record_not_found".to_string()),
+            message: "Record not found".to_string(),
+            errors: None,
+            status: None,
+        });
+        assert!(is_symbol_error(&not_found));
+    }
+
     #[test]
     fn request_to_query_pairs_deduplicates() {
         let request = MarketDataRequest::new()
@@ -273,6 +555,17 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn request_adds_crypto_symbols() {
+        let mut request = MarketDataRequest::new().with_crypto("BTC/USD");
+        request.add_crypto("ETH/USD");
+
+        assert_eq!(
+            request.to_query_pairs(),
+            vec![("cryptocurrency".to_string(), "BTC/USD,ETH/USD".to_string())]
+        );
+    }
+
     #[test]
     fn market_data_item_deserializes_core_fields() {
         let json = json!({
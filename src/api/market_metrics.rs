@@ -0,0 +1,309 @@
+//! Volatility, liquidity and earnings metrics from `/market-metrics`.
+//!
+//! # Example
+//! ```ignore
+//! for metrics in client.market_metrics(&["AAPL", "SPY", "TSLA"]).await? {
+//!     println!(
+//!         "{}: IV {:?} rank {:?} next earnings {:?}",
+//!         metrics.symbol.0,
+//!         metrics.implied_volatility_index,
+//!         metrics.implied_volatility_index_rank,
+//!         metrics.next_earnings_date()
+//!     );
+//! }
+//! ```
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::api::base::{Items, Result};
+use crate::TastyTrade;
+
+use super::instrument::{OptionChainType, SettlementType};
+use super::order::{AsSymbol, Symbol};
+
+/// Symbols sent per `/market-metrics` request
+pub const MARKET_METRICS_CHUNK_SIZE: usize = 100;
+
+impl TastyTrade {
+    /// Fetch market metrics for `symbols`, splitting large lists into
+    /// requests of [`MARKET_METRICS_CHUNK_SIZE`] symbols.
+    ///
+    /// Symbols the API has no metrics for are left out of the result.
+    pub async fn market_metrics(&self, symbols: &[impl AsSymbol]) -> Result<Vec<MarketMetrics>> {
+        let mut metrics = Vec::with_capacity(symbols.len());
+        for chunk in symbol_chunks(symbols) {
+            let resp: Items<MarketMetrics> = self
+                .get_with_query("/market-metrics", &[("symbols", chunk.as_str())])
+                .await?;
+            metrics.extend(resp.items);
+        }
+        Ok(metrics)
+    }
+}
+
+/// Comma-joined `symbols` query values, one per request.
+fn symbol_chunks(symbols: &[impl AsSymbol]) -> Vec<String> {
+    symbols
+        .chunks(MARKET_METRICS_CHUNK_SIZE)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|s| s.as_symbol().0)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect()
+}
+
+/// Metrics for one symbol.
+///
+/// Ranks and percentiles are fractions between 0 and 1; volatilities are
+/// annualized fractions (0.25 is 25%).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MarketMetrics {
+    pub symbol: Symbol,
+
+    /// Implied volatility index, blended across expirations
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub implied_volatility_index: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub implied_volatility_index_5_day_change: Option<Decimal>,
+
+    /// IV rank as shown in the tastytrade platform
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub implied_volatility_index_rank: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub tos_implied_volatility_index_rank: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub tw_implied_volatility_index_rank: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub implied_volatility_percentile: Option<Decimal>,
+
+    pub implied_volatility_updated_at: Option<DateTime<Utc>>,
+
+    /// Per-expiration implied volatilities
+    #[serde(default)]
+    pub option_expiration_implied_volatilities: Vec<ExpirationImpliedVolatility>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub historical_volatility_30_day: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub historical_volatility_60_day: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub historical_volatility_90_day: Option<Decimal>,
+
+    /// 30-day implied minus historical volatility
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub iv_hv_30_day_difference: Option<Decimal>,
+
+    /// Option liquidity rating from 0 (illiquid) to 4
+    pub liquidity_rating: Option<u32>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub liquidity_value: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub liquidity_rank: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub beta: Option<Decimal>,
+
+    pub beta_updated_at: Option<DateTime<Utc>>,
+
+    /// Three-month correlation with SPY
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub corr_spy_3month: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub market_cap: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub price_earnings_ratio: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub earnings_per_share: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub dividend_rate_per_share: Option<Decimal>,
+
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub dividend_yield: Option<Decimal>,
+
+    pub dividend_ex_date: Option<NaiveDate>,
+
+    pub dividend_next_date: Option<NaiveDate>,
+
+    pub dividend_pay_date: Option<NaiveDate>,
+
+    /// Upcoming or most recent earnings report
+    pub earnings: Option<EarningsInfo>,
+
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl MarketMetrics {
+    /// The expected date of the next earnings report, if one is scheduled.
+    pub fn next_earnings_date(&self) -> Option<NaiveDate> {
+        self.earnings.as_ref()?.expected_report_date
+    }
+
+    /// The implied volatility of the expiration on `date`.
+    pub fn implied_volatility_for(&self, date: NaiveDate) -> Option<Decimal> {
+        self.option_expiration_implied_volatilities
+            .iter()
+            .find(|e| e.expiration_date == date)?
+            .implied_volatility
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExpirationImpliedVolatility {
+    pub expiration_date: NaiveDate,
+    pub option_chain_type: Option<OptionChainType>,
+    pub settlement_type: Option<SettlementType>,
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub implied_volatility: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EarningsInfo {
+    pub visible: Option<bool>,
+    pub expected_report_date: Option<NaiveDate>,
+    /// Whether the report date is an estimate rather than confirmed
+    pub estimated: Option<bool>,
+    /// "BTO" (before the open) or "AMC" (after the close)
+    pub time_of_day: Option<String>,
+    pub quarter_end_date: Option<NaiveDate>,
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub actual_eps: Option<Decimal>,
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub consensus_estimate: Option<Decimal>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::dec;
+    use serde_json::json;
+
+    #[test]
+    fn test_market_metrics_deserialization() {
+        let json = json!({
+            "items": [
+                {
+                    "symbol": "AAPL",
+                    "implied-volatility-index": "0.2318",
+                    "implied-volatility-index-5-day-change": "-0.0112",
+                    "implied-volatility-index-rank": "0.1845",
+                    "tos-implied-volatility-index-rank": "0.1845",
+                    "tw-implied-volatility-index-rank": "0.2012",
+                    "implied-volatility-percentile": "0.31",
+                    "implied-volatility-updated-at": "2024-01-18T20:59:59.584Z",
+                    "liquidity-value": "0.0134",
+                    "liquidity-rank": "0.071",
+                    "liquidity-rating": 4,
+                    "updated-at": "2024-01-18T21:05:00.000Z",
+                    "option-expiration-implied-volatilities": [
+                        {
+                            "expiration-date": "2024-01-19",
+                            "option-chain-type": "Standard",
+                            "settlement-type": "PM",
+                            "implied-volatility": "0.1982"
+                        },
+                        {
+                            "expiration-date": "2024-02-16",
+                            "option-chain-type": "Standard",
+                            "settlement-type": "PM",
+                            "implied-volatility": "0.2245"
+                        }
+                    ],
+                    "beta": "1.29",
+                    "corr-spy-3month": "0.76",
+                    "market-cap": 2_900_000_000_000_i64,
+                    "price-earnings-ratio": "29.7",
+                    "earnings-per-share": "6.13",
+                    "dividend-rate-per-share": "0.96",
+                    "dividend-yield": "0.0051",
+                    "dividend-ex-date": "2023-11-10",
+                    "dividend-pay-date": "2023-11-16",
+                    "historical-volatility-30-day": "0.1587",
+                    "iv-hv-30-day-difference": "0.0731",
+                    "earnings": {
+                        "visible": true,
+                        "expected-report-date": "2024-02-01",
+                        "estimated": false,
+                        "time-of-day": "AMC",
+                        "late-flag": 0,
+                        "quarter-end-date": "2023-12-31",
+                        "actual-eps": "1.46",
+                        "consensus-estimate": "2.1"
+                    }
+                },
+                { "symbol": "NEWCO" }
+            ]
+        });
+
+        let items: Items<MarketMetrics> = serde_json::from_value(json).unwrap();
+        let aapl = &items.items[0];
+        assert_eq!(aapl.symbol.0, "AAPL");
+        assert_eq!(aapl.implied_volatility_index, Some(dec("0.2318")));
+        assert_eq!(
+            aapl.implied_volatility_index_5_day_change,
+            Some(dec("-0.0112"))
+        );
+        assert_eq!(aapl.implied_volatility_index_rank, Some(dec("0.1845")));
+        assert_eq!(aapl.implied_volatility_percentile, Some(dec("0.31")));
+        assert_eq!(aapl.liquidity_rating, Some(4));
+        assert_eq!(aapl.beta, Some(dec("1.29")));
+        assert_eq!(aapl.corr_spy_3month, Some(dec("0.76")));
+        assert_eq!(aapl.market_cap, Some(Decimal::from(2_900_000_000_000_i64)));
+        assert_eq!(aapl.dividend_ex_date, NaiveDate::from_ymd_opt(2023, 11, 10));
+        assert_eq!(
+            aapl.next_earnings_date(),
+            NaiveDate::from_ymd_opt(2024, 2, 1)
+        );
+        assert_eq!(
+            aapl.earnings.as_ref().unwrap().time_of_day.as_deref(),
+            Some("AMC")
+        );
+        assert_eq!(
+            aapl.implied_volatility_for(NaiveDate::from_ymd_opt(2024, 2, 16).unwrap()),
+            Some(dec("0.2245"))
+        );
+        assert_eq!(
+            aapl.option_expiration_implied_volatilities[0].settlement_type,
+            Some(SettlementType::PM)
+        );
+        assert!(aapl.implied_volatility_updated_at.is_some());
+
+        let newco = &items.items[1];
+        assert!(newco.implied_volatility_index.is_none());
+        assert!(newco.option_expiration_implied_volatilities.is_empty());
+        assert!(newco.next_earnings_date().is_none());
+    }
+
+    #[test]
+    fn test_symbol_chunks() {
+        let symbols: Vec<String> = (0..250).map(|i| format!("S{i}")).collect();
+        let chunks = symbol_chunks(&symbols);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].split(',').count(), MARKET_METRICS_CHUNK_SIZE);
+        assert_eq!(chunks[2].split(',').count(), 50);
+        assert!(chunks[0].starts_with("S0,S1,"));
+        assert!(chunks[2].ends_with(",S249"));
+
+        assert!(symbol_chunks(&[] as &[&str]).is_empty());
+    }
+}
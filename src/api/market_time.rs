@@ -0,0 +1,386 @@
+//! Market sessions and holiday calendars from `/market-time`.
+//!
+//! Sessions are grouped by instrument collection ("Equity", "CME", "CFE",
+//! ...); equities report theirs in
+//! [`EquityInstrumentInfo::market_time_instrument_collection`](super::instrument::EquityInstrumentInfo).
+//! [`MarketCalendar`] answers open/closed questions from a list of sessions.
+//!
+//! # Example
+//! ```ignore
+//! let calendar = client.current_market_calendar("Equity").await?;
+//! let now = Utc::now();
+//! if calendar.is_open(now) {
+//!     println!("{} minutes to the close", calendar.minutes_to_close(now).unwrap());
+//! } else {
+//!     println!("next open at {:?}", calendar.next_open(now));
+//! }
+//! ```
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use serde::Deserialize;
+
+use crate::api::base::{Items, Result};
+use crate::TastyTrade;
+
+use super::instrument::encode_path_segment;
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum MarketTimeError {
+    #[error("no market session found for {0}")]
+    NoSession(String),
+}
+
+impl TastyTrade {
+    /// The current session of `instrument_collection`, with the previous and next sessions.
+    pub async fn current_market_session(
+        &self,
+        instrument_collection: &str,
+    ) -> Result<MarketSession> {
+        let resp: Items<MarketSession> = self
+            .get_with_query(
+                "/market-time/sessions/current",
+                &[("instrument-collections[]", instrument_collection)],
+            )
+            .await?;
+        resp.items
+            .into_iter()
+            .find(|s| {
+                s.instrument_collection
+                    .as_deref()
+                    .is_none_or(|c| c == instrument_collection)
+            })
+            .ok_or_else(|| MarketTimeError::NoSession(instrument_collection.to_string()).into())
+    }
+
+    /// Sessions of `instrument_collection` from `start_date` to `end_date` inclusive.
+    pub async fn market_sessions(
+        &self,
+        instrument_collection: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<MarketSession>> {
+        let resp: Items<MarketSession> = self
+            .get_with_query(
+                "/market-time/sessions",
+                &[
+                    ("instrument-collections[]", instrument_collection),
+                    ("start-date", &start_date.format("%Y-%m-%d").to_string()),
+                    ("end-date", &end_date.format("%Y-%m-%d").to_string()),
+                ],
+            )
+            .await?;
+        Ok(resp.items)
+    }
+
+    /// Equity market holidays and half days.
+    pub async fn equity_holidays(&self) -> Result<HolidayCalendar> {
+        self.get("/market-time/equities/holidays").await
+    }
+
+    /// Holidays and half days of a futures instrument collection such as "CME".
+    pub async fn futures_holidays(&self, instrument_collection: &str) -> Result<HolidayCalendar> {
+        self.get(format!(
+            "/market-time/futures/holidays/{}",
+            encode_path_segment(instrument_collection)
+        ))
+        .await
+    }
+
+    /// A calendar around the current session of `instrument_collection`.
+    pub async fn current_market_calendar(
+        &self,
+        instrument_collection: &str,
+    ) -> Result<MarketCalendar> {
+        let session = self.current_market_session(instrument_collection).await?;
+        Ok(MarketCalendar::from_current(instrument_collection, session))
+    }
+
+    /// A calendar of the sessions of `instrument_collection` between two dates.
+    pub async fn market_calendar(
+        &self,
+        instrument_collection: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<MarketCalendar> {
+        let sessions = self
+            .market_sessions(instrument_collection, start_date, end_date)
+            .await?;
+        Ok(MarketCalendar::new(instrument_collection, sessions))
+    }
+}
+
+/// Trading state of a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum SessionState {
+    Open,
+    Closed,
+    #[serde(rename = "Pre-Market", alias = "Pre-Trading")]
+    PreMarket,
+    #[serde(rename = "Extended", alias = "After-Hours")]
+    Extended,
+    #[serde(other)]
+    Unknown,
+}
+
+/// One trading session. Times are UTC.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MarketSession {
+    pub instrument_collection: Option<String>,
+    pub session_date: Option<NaiveDate>,
+    /// Start of pre-market trading
+    pub start_at: Option<DateTime<Utc>>,
+    /// Regular session open
+    pub open_at: Option<DateTime<Utc>>,
+    /// Regular session close
+    pub close_at: Option<DateTime<Utc>>,
+    /// End of extended-hours trading
+    pub close_at_ext: Option<DateTime<Utc>>,
+    pub state: Option<SessionState>,
+    pub next_session: Option<Box<MarketSession>>,
+    pub previous_session: Option<Box<MarketSession>>,
+}
+
+impl MarketSession {
+    /// Whether `now` falls in the regular session.
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        match (self.open_at, self.close_at) {
+            (Some(open), Some(close)) => open <= now && now < close,
+            _ => false,
+        }
+    }
+
+    /// The session's trading date, falling back to the date of the open.
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.session_date
+            .or_else(|| self.open_at.map(|t| t.date_naive()))
+    }
+}
+
+/// Full-day holidays and early-close days.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HolidayCalendar {
+    #[serde(default)]
+    pub market_holidays: Vec<NaiveDate>,
+    #[serde(default)]
+    pub market_half_days: Vec<NaiveDate>,
+}
+
+impl HolidayCalendar {
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.market_holidays.contains(&date)
+    }
+
+    pub fn is_half_day(&self, date: NaiveDate) -> bool {
+        self.market_half_days.contains(&date)
+    }
+
+    /// Whether the market trades on `date`: a weekday that is not a holiday.
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+
+    /// The last trading day before `date`, skipping weekends and holidays.
+    pub fn previous_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut day = date;
+        while let Some(previous) = day.pred_opt() {
+            day = previous;
+            if self.is_trading_day(day) {
+                break;
+            }
+        }
+        day
+    }
+}
+
+/// The known sessions of one instrument collection, in chronological order.
+#[derive(Debug, Clone)]
+pub struct MarketCalendar {
+    pub instrument_collection: String,
+    pub sessions: Vec<MarketSession>,
+    pub holidays: HolidayCalendar,
+}
+
+impl MarketCalendar {
+    pub fn new(instrument_collection: impl Into<String>, mut sessions: Vec<MarketSession>) -> Self {
+        sessions.retain(|s| s.open_at.is_some() && s.close_at.is_some());
+        sessions.sort_by_key(|s| s.open_at);
+        sessions.dedup_by_key(|s| s.open_at);
+        Self {
+            instrument_collection: instrument_collection.into(),
+            sessions,
+            holidays: HolidayCalendar::default(),
+        }
+    }
+
+    /// A calendar of the current session and its neighbours, as returned by
+    /// [`TastyTrade::current_market_session`].
+    pub fn from_current(
+        instrument_collection: impl Into<String>,
+        mut current: MarketSession,
+    ) -> Self {
+        let mut sessions = Vec::with_capacity(3);
+        if let Some(previous) = current.previous_session.take() {
+            sessions.push(*previous);
+        }
+        if let Some(next) = current.next_session.take() {
+            sessions.push(*next);
+        }
+        sessions.push(current);
+        Self::new(instrument_collection, sessions)
+    }
+
+    pub fn with_holidays(mut self, holidays: HolidayCalendar) -> Self {
+        self.holidays = holidays;
+        self
+    }
+
+    /// The regular session in progress at `now`.
+    pub fn session_at(&self, now: DateTime<Utc>) -> Option<&MarketSession> {
+        self.sessions.iter().find(|s| s.contains(now))
+    }
+
+    /// Whether the regular session is open at `now`.
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        self.session_at(now).is_some()
+    }
+
+    /// The first regular open after `now`, if the calendar reaches that far.
+    pub fn next_open(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.sessions
+            .iter()
+            .filter_map(|s| s.open_at)
+            .find(|open| *open > now)
+    }
+
+    /// Whole minutes until the regular close, or `None` while the market is closed.
+    pub fn minutes_to_close(&self, now: DateTime<Utc>) -> Option<i64> {
+        let close = self.session_at(now)?.close_at?;
+        Some((close - now).num_minutes())
+    }
+
+    /// Whether `date` is a listed holiday, or a weekday within the calendar's
+    /// range that has no session.
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        if self.holidays.is_holiday(date) {
+            return true;
+        }
+        let (Some(first), Some(last)) = (
+            self.sessions.first().and_then(MarketSession::date),
+            self.sessions.last().and_then(MarketSession::date),
+        ) else {
+            return false;
+        };
+        first <= date
+            && date <= last
+            && !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            && !self.sessions.iter().any(|s| s.date() == Some(date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::date;
+    use serde_json::json;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_current_session_deserialization() {
+        let json = json!({
+            "items": [{
+                "instrument-collection": "Equity",
+                "start-at": "2024-07-03T08:00:00Z",
+                "open-at": "2024-07-03T13:30:00Z",
+                "close-at": "2024-07-03T17:00:00Z",
+                "close-at-ext": "2024-07-03T21:00:00Z",
+                "state": "Open",
+                "previous-session": {
+                    "open-at": "2024-07-02T13:30:00Z",
+                    "close-at": "2024-07-02T20:00:00Z",
+                    "session-date": "2024-07-02"
+                },
+                "next-session": {
+                    "open-at": "2024-07-05T13:30:00Z",
+                    "close-at": "2024-07-05T20:00:00Z",
+                    "session-date": "2024-07-05"
+                }
+            }]
+        });
+
+        let items: Items<MarketSession> = serde_json::from_value(json).unwrap();
+        let session = items.items.into_iter().next().unwrap();
+        assert_eq!(session.state, Some(SessionState::Open));
+        assert_eq!(session.date(), Some(date(2024, 7, 3)));
+        assert_eq!(
+            session.next_session.as_ref().unwrap().session_date,
+            Some(date(2024, 7, 5))
+        );
+
+        let calendar = MarketCalendar::from_current("Equity", session);
+        assert_eq!(calendar.sessions.len(), 3);
+        assert_eq!(calendar.sessions[0].date(), Some(date(2024, 7, 2)));
+
+        // Half day: open until 17:00 UTC
+        let now = at("2024-07-03T16:15:30Z");
+        assert!(calendar.is_open(now));
+        assert_eq!(calendar.minutes_to_close(now), Some(44));
+        assert_eq!(calendar.next_open(now), Some(at("2024-07-05T13:30:00Z")));
+
+        // After the close, over the holiday
+        let now = at("2024-07-04T15:00:00Z");
+        assert!(!calendar.is_open(now));
+        assert!(calendar.minutes_to_close(now).is_none());
+        assert_eq!(calendar.next_open(now), Some(at("2024-07-05T13:30:00Z")));
+        assert!(calendar.is_holiday(date(2024, 7, 4)));
+        assert!(!calendar.is_holiday(date(2024, 7, 5)));
+
+        // Beyond the known sessions
+        assert!(calendar.next_open(at("2024-07-05T14:00:00Z")).is_none());
+    }
+
+    #[test]
+    fn test_holiday_calendar() {
+        let holidays: HolidayCalendar = serde_json::from_value(json!({
+            "market-holidays": ["2024-01-01", "2024-01-15", "2024-07-04"],
+            "market-half-days": ["2024-07-03", "2024-11-29"]
+        }))
+        .unwrap();
+        assert!(holidays.is_holiday(date(2024, 7, 4)));
+        assert!(holidays.is_half_day(date(2024, 11, 29)));
+        assert!(!holidays.is_holiday(date(2024, 7, 5)));
+        // Tuesday after MLK day steps back over the holiday and the weekend
+        assert_eq!(
+            holidays.previous_trading_day(date(2024, 1, 16)),
+            date(2024, 1, 12)
+        );
+        assert_eq!(
+            holidays.previous_trading_day(date(2024, 7, 5)),
+            date(2024, 7, 3)
+        );
+
+        let sessions: Vec<MarketSession> = serde_json::from_value(json!([
+            {
+                "session-date": "2024-01-16",
+                "open-at": "2024-01-16T14:30:00Z",
+                "close-at": "2024-01-16T21:00:00Z"
+            },
+            {
+                "session-date": "2024-01-12",
+                "open-at": "2024-01-12T14:30:00Z",
+                "close-at": "2024-01-12T21:00:00Z"
+            }
+        ]))
+        .unwrap();
+        let calendar = MarketCalendar::new("Equity", sessions).with_holidays(holidays);
+        assert_eq!(calendar.sessions[0].date(), Some(date(2024, 1, 12)));
+        assert!(calendar.is_holiday(date(2024, 1, 15)));
+        assert!(calendar.is_holiday(date(2024, 7, 4)));
+        // Weekends are not holidays
+        assert!(!calendar.is_holiday(date(2024, 1, 13)));
+    }
+}
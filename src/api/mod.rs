@@ -2,12 +2,34 @@ pub mod account_streaming;
 pub mod accounts;
 pub mod auth;
 pub mod base;
+pub mod bulk_orders;
+pub mod chain_query;
+pub mod corporate_events;
+pub mod crypto;
 pub mod event;
+pub mod future_symbol;
+pub mod futures;
+pub mod implied_volatility;
 pub mod instrument;
 pub mod market_data;
+pub mod market_metrics;
+pub mod market_time;
 pub mod oauth2;
 pub mod option_chain;
+pub mod option_pricing;
+pub mod option_symbol;
 pub mod order;
+pub mod order_description;
+pub mod order_legs;
+pub mod order_tracker;
+pub mod order_validation;
+pub mod paper;
 pub mod position;
 pub mod quote_streaming;
+pub mod repricer;
+pub mod risk;
+pub mod roll;
 pub mod transaction;
+
+#[cfg(test)]
+mod test_util;
@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::api::base::Result;
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use serde_json::Value;
@@ -9,6 +10,8 @@ use crate::TastyTrade;
 
 use super::{
     base::Items,
+    instrument::{ExerciseStyle, ExpirationType, OptionChainType, OptionType, SettlementType},
+    option_symbol::OptionSymbol,
     order::{AsSymbol, Symbol},
     quote_streaming::DxFeedSymbol,
 };
@@ -28,6 +31,7 @@ impl TastyTrade {
                 message: format!("No option chain found for symbol: {}", symbol.0),
                 code: None,
                 errors: None,
+                status: None,
             }
             .into());
         }
@@ -42,6 +46,27 @@ impl TastyTrade {
         Ok(resp.items)
     }
 
+    /// Fetch the compact chain: plain symbol lists per option root, a fraction
+    /// of the size of the full chains for heavy underlyings such as SPX.
+    ///
+    /// # Example
+    /// ```ignore
+    /// for root in client.compact_option_chain_for("SPX").await? {
+    ///     for (expiration, options) in root.by_expiration() {
+    ///         println!("{} {}: {} contracts", root.root_symbol.0, expiration, options.len());
+    ///     }
+    /// }
+    /// ```
+    pub async fn compact_option_chain_for(
+        &self,
+        symbol: impl Into<Symbol>,
+    ) -> Result<Vec<CompactOptionChain>> {
+        let resp: Items<CompactOptionChain> = self
+            .get(format!("/option-chains/{}/compact", symbol.into().0))
+            .await?;
+        Ok(resp.items)
+    }
+
     pub async fn get_option_info(&self, symbol: impl AsSymbol) -> Result<OptionInfo> {
         self.get(format!(
             "/instruments/equity-options/{}",
@@ -62,21 +87,66 @@ pub struct OptionInfo {
 pub struct NestedOptionChain {
     pub underlying_symbol: Symbol,
     pub root_symbol: Symbol,
-    pub option_chain_type: String,
+    pub option_chain_type: OptionChainType,
     pub shares_per_contract: u64,
     pub expirations: Vec<Expiration>,
 }
 
+impl NestedOptionChain {
+    /// The expiration listed for `date`, if any.
+    pub fn expiration_on(&self, date: NaiveDate) -> Option<&Expiration> {
+        self.expirations.iter().find(|e| e.expiration_date == date)
+    }
+
+    /// The soonest expiration at least `min_dte` days out.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let chain = client.nested_option_chain_for("SPY").await?;
+    /// if let Some(expiration) = chain.nearest_expiration(30) {
+    ///     for strike in expiration.strikes_between(dec!(440), dec!(460)) {
+    ///         println!("{} {}", expiration.expiration_date, strike.strike_price);
+    ///     }
+    /// }
+    /// ```
+    pub fn nearest_expiration(&self, min_dte: u64) -> Option<&Expiration> {
+        self.expirations
+            .iter()
+            .filter(|e| e.days_to_expiration >= min_dte)
+            .min_by_key(|e| e.expiration_date)
+    }
+
+    /// Expirations in date order.
+    pub fn sorted_expirations(&self) -> Vec<&Expiration> {
+        let mut expirations: Vec<&Expiration> = self.expirations.iter().collect();
+        expirations.sort_by_key(|e| e.expiration_date);
+        expirations
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Expiration {
-    pub expiration_type: String,
-    pub expiration_date: String,
+    pub expiration_type: ExpirationType,
+    pub expiration_date: NaiveDate,
     pub days_to_expiration: u64,
-    pub settlement_type: String,
+    pub settlement_type: SettlementType,
     pub strikes: Vec<Strike>,
 }
 
+impl Expiration {
+    /// Strikes from `lo` to `hi` inclusive, in ascending order.
+    pub fn strikes_between(&self, lo: Decimal, hi: Decimal) -> Vec<&Strike> {
+        let mut strikes: Vec<&Strike> = self
+            .strikes
+            .iter()
+            .filter(|s| s.strike_price >= lo && s.strike_price <= hi)
+            .collect();
+        strikes.sort_by_key(|s| s.strike_price);
+        strikes
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Strike {
@@ -86,16 +156,86 @@ pub struct Strike {
     pub put: Symbol,
 }
 
+/// A single contract from the flat `/option-chains/{symbol}` listing.
+///
+/// Fields the crate does not model are kept in `extra`.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct OptionChain {
     pub underlying_symbol: Symbol,
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
     pub strike_price: Decimal,
+    /// OCC symbol (e.g., "AAPL  240119C00150000")
+    pub symbol: Option<Symbol>,
+    pub root_symbol: Option<Symbol>,
+    pub expiration_date: Option<NaiveDate>,
+    pub option_type: Option<OptionType>,
+    pub days_to_expiration: Option<u64>,
+    pub expiration_type: Option<ExpirationType>,
+    pub settlement_type: Option<SettlementType>,
+    pub exercise_style: Option<ExerciseStyle>,
+    pub shares_per_contract: Option<u64>,
+    pub streamer_symbol: Option<DxFeedSymbol>,
+    pub active: Option<bool>,
+    pub is_closing_only: Option<bool>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub stops_trading_at: Option<DateTime<Utc>>,
     #[serde(flatten)]
     pub extra: HashMap<String, Value>,
 }
 
+impl OptionChain {
+    /// The contract's parsed OCC symbol, if the listing included one.
+    pub fn option_symbol(&self) -> Option<OptionSymbol> {
+        self.symbol.as_ref()?.to_option_symbol().ok()
+    }
+}
+
+/// Symbol lists for one option root from `/option-chains/{symbol}/compact`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CompactOptionChain {
+    pub underlying_symbol: Symbol,
+    pub root_symbol: Symbol,
+    pub option_chain_type: Option<OptionChainType>,
+    pub settlement_type: Option<SettlementType>,
+    pub shares_per_contract: Option<u64>,
+    pub expiration_type: Option<ExpirationType>,
+    /// OCC symbols of every contract on this root
+    #[serde(default)]
+    pub symbols: Vec<Symbol>,
+    /// Streamer symbols, in the same order as `symbols`
+    #[serde(default)]
+    pub streamer_symbols: Vec<DxFeedSymbol>,
+}
+
+impl CompactOptionChain {
+    /// Contracts grouped by expiration date; symbols that fail to parse are skipped.
+    pub fn by_expiration(&self) -> BTreeMap<NaiveDate, Vec<OptionSymbol>> {
+        let mut grouped: BTreeMap<NaiveDate, Vec<OptionSymbol>> = BTreeMap::new();
+        for option in self
+            .symbols
+            .iter()
+            .filter_map(|s| s.to_option_symbol().ok())
+        {
+            grouped.entry(option.expiration).or_default().push(option);
+        }
+        for options in grouped.values_mut() {
+            options.sort_by(|a, b| {
+                a.strike
+                    .cmp(&b.strike)
+                    .then_with(|| a.is_call().cmp(&b.is_call()).reverse())
+            });
+        }
+        grouped
+    }
+
+    /// Pairs of OCC symbol and streamer symbol, for subscribing and mapping events back.
+    pub fn symbol_pairs(&self) -> impl Iterator<Item = (&Symbol, &DxFeedSymbol)> {
+        self.symbols.iter().zip(&self.streamer_symbols)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,16 +288,19 @@ mod tests {
         let chain: NestedOptionChain = serde_json::from_value(json).unwrap();
         assert_eq!(chain.underlying_symbol.0, "AAPL");
         assert_eq!(chain.root_symbol.0, "AAPL");
-        assert_eq!(chain.option_chain_type, "Standard");
+        assert_eq!(chain.option_chain_type, OptionChainType::Standard);
         assert_eq!(chain.shares_per_contract, 100);
         assert_eq!(chain.expirations.len(), 2);
 
         // Test first expiration
         let exp1 = &chain.expirations[0];
-        assert_eq!(exp1.expiration_type, "Regular");
-        assert_eq!(exp1.expiration_date, "2024-01-19");
+        assert_eq!(exp1.expiration_type, ExpirationType::Regular);
+        assert_eq!(
+            exp1.expiration_date,
+            NaiveDate::from_ymd_opt(2024, 1, 19).unwrap()
+        );
         assert_eq!(exp1.days_to_expiration, 30);
-        assert_eq!(exp1.settlement_type, "PM");
+        assert_eq!(exp1.settlement_type, SettlementType::PM);
         assert_eq!(exp1.strikes.len(), 2);
 
         // Test strike prices with high precision
@@ -177,10 +320,13 @@ mod tests {
 
         // Test second expiration
         let exp2 = &chain.expirations[1];
-        assert_eq!(exp2.expiration_type, "Weekly");
-        assert_eq!(exp2.expiration_date, "2024-01-12");
+        assert_eq!(exp2.expiration_type, ExpirationType::Weekly);
+        assert_eq!(
+            exp2.expiration_date,
+            NaiveDate::from_ymd_opt(2024, 1, 12).unwrap()
+        );
         assert_eq!(exp2.days_to_expiration, 23);
-        assert_eq!(exp2.settlement_type, "PM");
+        assert_eq!(exp2.settlement_type, SettlementType::PM);
         assert_eq!(exp2.strikes.len(), 1);
         assert_eq!(
             exp2.strikes[0].strike_price,
@@ -309,12 +455,13 @@ mod tests {
         let chain: NestedOptionChain = serde_json::from_value(json).unwrap();
         assert_eq!(chain.underlying_symbol.0, "SPX");
         assert_eq!(chain.root_symbol.0, "SPXW");
-        assert_eq!(chain.option_chain_type, "Index");
+        // Chain types the crate does not model fall back to Unknown
+        assert_eq!(chain.option_chain_type, OptionChainType::Unknown);
         assert_eq!(chain.shares_per_contract, 1); // Index options typically have multiplier of 1
 
         let exp = &chain.expirations[0];
-        assert_eq!(exp.expiration_type, "AM");
-        assert_eq!(exp.settlement_type, "AM");
+        assert_eq!(exp.expiration_type, ExpirationType::Unknown);
+        assert_eq!(exp.settlement_type, SettlementType::AM);
         assert_eq!(exp.strikes.len(), 3);
 
         // Test decimal precision for different strike prices
@@ -336,6 +483,85 @@ mod tests {
         assert_eq!(exp.strikes[2].put.0, "SPXW 240201P04550500");
     }
 
+    #[test]
+    fn test_expiration_accessors() {
+        let strikes = |date: &str| {
+            json!([
+                { "strike-price": "110", "call": format!("SPY   {date}C00110000"), "put": format!("SPY   {date}P00110000") },
+                { "strike-price": "100", "call": format!("SPY   {date}C00100000"), "put": format!("SPY   {date}P00100000") },
+                { "strike-price": "105", "call": format!("SPY   {date}C00105000"), "put": format!("SPY   {date}P00105000") },
+                { "strike-price": "95", "call": format!("SPY   {date}C00095000"), "put": format!("SPY   {date}P00095000") }
+            ])
+        };
+        let json = json!({
+            "underlying-symbol": "SPY",
+            "root-symbol": "SPY",
+            "option-chain-type": "Non-standard",
+            "shares-per-contract": 100,
+            "expirations": [
+                {
+                    "expiration-type": "End-Of-Month",
+                    "expiration-date": "2024-02-29",
+                    "days-to-expiration": 45,
+                    "settlement-type": "PM",
+                    "strikes": strikes("240229")
+                },
+                {
+                    "expiration-type": "Quarterly",
+                    "expiration-date": "2024-03-28",
+                    "days-to-expiration": 73,
+                    "settlement-type": "PM",
+                    "strikes": strikes("240328")
+                },
+                {
+                    "expiration-type": "Weekly",
+                    "expiration-date": "2024-01-19",
+                    "days-to-expiration": 4,
+                    "settlement-type": "PM",
+                    "strikes": strikes("240119")
+                }
+            ]
+        });
+
+        let chain: NestedOptionChain = serde_json::from_value(json).unwrap();
+        assert_eq!(chain.option_chain_type, OptionChainType::NonStandard);
+
+        let eom = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let expiration = chain.expiration_on(eom).unwrap();
+        assert_eq!(expiration.expiration_type, ExpirationType::EndOfMonth);
+        assert!(chain
+            .expiration_on(NaiveDate::from_ymd_opt(2024, 2, 28).unwrap())
+            .is_none());
+
+        assert_eq!(chain.nearest_expiration(0).unwrap().days_to_expiration, 4);
+        assert_eq!(chain.nearest_expiration(30).unwrap().expiration_date, eom);
+        assert_eq!(
+            chain.nearest_expiration(50).unwrap().expiration_type,
+            ExpirationType::Quarterly
+        );
+        assert!(chain.nearest_expiration(100).is_none());
+
+        let dates: Vec<_> = chain
+            .sorted_expirations()
+            .iter()
+            .map(|e| e.days_to_expiration)
+            .collect();
+        assert_eq!(dates, vec![4, 45, 73]);
+
+        let strikes: Vec<_> = expiration
+            .strikes_between(Decimal::from(100), Decimal::from(110))
+            .iter()
+            .map(|s| s.strike_price)
+            .collect();
+        assert_eq!(
+            strikes,
+            vec![Decimal::from(100), Decimal::from(105), Decimal::from(110)]
+        );
+        assert!(expiration
+            .strikes_between(Decimal::from(111), Decimal::from(120))
+            .is_empty());
+    }
+
     #[test]
     fn test_option_chain_empty_extra_fields() {
         // Test that OptionChain works even with minimal required fields
@@ -350,6 +576,85 @@ mod tests {
         assert!(chain.extra.is_empty()); // No extra fields should result in empty HashMap
     }
 
+    #[test]
+    fn test_option_chain_typed_fields() {
+        let json = json!({
+            "underlying-symbol": "AAPL",
+            "strike-price": "150.0",
+            "symbol": "AAPL  240119C00150000",
+            "root-symbol": "AAPL",
+            "expiration-date": "2024-01-19",
+            "option-type": "C",
+            "days-to-expiration": 30,
+            "expiration-type": "Regular",
+            "settlement-type": "PM",
+            "exercise-style": "American",
+            "shares-per-contract": 100,
+            "streamer-symbol": ".AAPL240119C150",
+            "active": true,
+            "is-closing-only": false,
+            "expires-at": "2024-01-19T21:00:00.000+00:00",
+            "instrument-type": "Equity Option"
+        });
+
+        let chain: OptionChain = serde_json::from_value(json).unwrap();
+        assert_eq!(chain.expiration_date, NaiveDate::from_ymd_opt(2024, 1, 19));
+        assert_eq!(chain.option_type, Some(OptionType::Call));
+        assert_eq!(chain.days_to_expiration, Some(30));
+        assert_eq!(chain.settlement_type, Some(SettlementType::PM));
+        assert_eq!(chain.expiration_type, Some(ExpirationType::Regular));
+        assert_eq!(chain.streamer_symbol.as_ref().unwrap().0, ".AAPL240119C150");
+        assert_eq!(chain.active, Some(true));
+        assert_eq!(chain.is_closing_only, Some(false));
+        assert_eq!(chain.option_symbol().unwrap().strike, Decimal::from(150));
+        // Typed fields are no longer duplicated in the extra map
+        assert_eq!(chain.extra.len(), 1);
+        assert!(chain.extra.contains_key("instrument-type"));
+    }
+
+    #[test]
+    fn test_compact_option_chain() {
+        let json = json!({
+            "items": [{
+                "underlying-symbol": "SPX",
+                "root-symbol": "SPXW",
+                "option-chain-type": "Standard",
+                "settlement-type": "PM",
+                "shares-per-contract": 100,
+                "expiration-type": "Weekly",
+                "symbols": [
+                    "SPXW  240119P04500000",
+                    "SPXW  240112C04500000",
+                    "SPXW  240112P04500000",
+                    "SPXW  240112C04490000",
+                    "not a symbol"
+                ],
+                "streamer-symbols": [
+                    ".SPXW240119P4500",
+                    ".SPXW240112C4500",
+                    ".SPXW240112P4500",
+                    ".SPXW240112C4490"
+                ]
+            }]
+        });
+
+        let items: Items<CompactOptionChain> = serde_json::from_value(json).unwrap();
+        let compact = &items.items[0];
+        let grouped = compact.by_expiration();
+        assert_eq!(grouped.len(), 2);
+
+        let first = &grouped[&NaiveDate::from_ymd_opt(2024, 1, 12).unwrap()];
+        assert_eq!(first.len(), 3);
+        assert_eq!(first[0].strike, Decimal::from(4490));
+        // Calls sort before puts at the same strike
+        assert!(first[1].is_call());
+        assert!(!first[2].is_call());
+
+        let pairs: Vec<_> = compact.symbol_pairs().collect();
+        assert_eq!(pairs.len(), 4);
+        assert_eq!(pairs[1].1 .0, ".SPXW240112C4500");
+    }
+
     #[test]
     fn test_dxfeed_symbol_serde() {
         let symbol = DxFeedSymbol("TEST123".to_string());
@@ -0,0 +1,517 @@
+//! Theoretical option prices and Greeks computed locally.
+//!
+//! European options use Black-Scholes-Merton with a continuous dividend
+//! yield; American options use a Cox-Ross-Rubinstein binomial tree, which
+//! captures early exercise of puts and of calls on dividend payers. Use these
+//! for scenario analysis at spots, dates and volatilities the streamed
+//! [`GreeksData`](super::quote_streaming::GreeksData) does not cover.
+//!
+//! Greeks follow the platform conventions: theta per calendar day, vega per
+//! volatility point and rho per percentage point of rate.
+//!
+//! # Example
+//! ```ignore
+//! let option: OptionSymbol = "AAPL  240119C00150000".parse()?;
+//! let inputs = PricingInputs::new(dec!(152.30), dec!(0.24), valuation_date).rate(dec!(0.05));
+//! let value = price_option(&option, &inputs, ExerciseStyle::American);
+//! println!("{} delta {:.2}", value.price, value.delta);
+//! ```
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+use super::instrument::{ExerciseStyle, OptionType};
+use super::option_symbol::OptionSymbol;
+
+/// Steps in the binomial tree used for American options
+pub const BINOMIAL_STEPS: usize = 200;
+
+/// Days per year used to turn days to expiration into years
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Decimal places kept in computed prices
+const PRICE_SCALE: u32 = 6;
+
+/// Market inputs for pricing a contract.
+///
+/// Rates, yields and volatilities are annualized fractions (0.05 is 5%).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PricingInputs {
+    /// Underlying price
+    pub spot: Decimal,
+    pub volatility: Decimal,
+    /// Continuously compounded risk-free rate
+    pub rate: Decimal,
+    /// Continuous dividend yield of the underlying
+    pub dividend_yield: Decimal,
+    /// Date to value the option at; time to expiration is measured from here
+    pub valuation_date: NaiveDate,
+}
+
+impl PricingInputs {
+    /// Inputs with zero rate and dividend yield.
+    pub fn new(spot: Decimal, volatility: Decimal, valuation_date: NaiveDate) -> Self {
+        Self {
+            spot,
+            volatility,
+            rate: Decimal::ZERO,
+            dividend_yield: Decimal::ZERO,
+            valuation_date,
+        }
+    }
+
+    pub fn rate(mut self, rate: Decimal) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    pub fn dividend_yield(mut self, dividend_yield: Decimal) -> Self {
+        self.dividend_yield = dividend_yield;
+        self
+    }
+
+    /// The same inputs at a different spot, for scenario grids.
+    pub fn with_spot(self, spot: Decimal) -> Self {
+        Self { spot, ..self }
+    }
+
+    /// The same inputs on a different valuation date.
+    pub fn on(self, valuation_date: NaiveDate) -> Self {
+        Self {
+            valuation_date,
+            ..self
+        }
+    }
+}
+
+/// Theoretical price and Greeks of one contract, per share.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionValue {
+    pub price: Decimal,
+    pub delta: f64,
+    pub gamma: f64,
+    /// Change in price per calendar day
+    pub theta: f64,
+    /// Change in price per volatility point
+    pub vega: f64,
+    /// Change in price per percentage point of rate
+    pub rho: f64,
+}
+
+/// Price `option` with the model that fits its exercise style.
+///
+/// European options use Black-Scholes; American and unknown styles use the
+/// binomial tree, since early exercise is never worth less.
+pub fn price_option(
+    option: &OptionSymbol,
+    inputs: &PricingInputs,
+    exercise_style: ExerciseStyle,
+) -> OptionValue {
+    match exercise_style {
+        ExerciseStyle::European => black_scholes(option, inputs),
+        ExerciseStyle::American | ExerciseStyle::Unknown => american(option, inputs),
+    }
+}
+
+/// Black-Scholes-Merton price and Greeks of a European option.
+pub fn black_scholes(option: &OptionSymbol, inputs: &PricingInputs) -> OptionValue {
+    Model::new(option, inputs).black_scholes()
+}
+
+/// Binomial-tree price and Greeks of an American option.
+pub fn american(option: &OptionSymbol, inputs: &PricingInputs) -> OptionValue {
+    Model::new(option, inputs).american(BINOMIAL_STEPS)
+}
+
+/// Calendar years from `valuation_date` to `expiration`, never negative.
+pub fn years_to_expiration(valuation_date: NaiveDate, expiration: NaiveDate) -> f64 {
+    ((expiration - valuation_date).num_days().max(0) as f64) / DAYS_PER_YEAR
+}
+
+/// Pricing inputs as floats.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Model {
+    pub call: bool,
+    pub spot: f64,
+    pub strike: f64,
+    /// Years to expiration
+    pub time: f64,
+    pub rate: f64,
+    pub dividend_yield: f64,
+    pub volatility: f64,
+}
+
+impl Model {
+    pub fn new(option: &OptionSymbol, inputs: &PricingInputs) -> Self {
+        let float = |d: Decimal| d.to_f64().unwrap_or(0.0);
+        Self {
+            call: option.option_type == OptionType::Call,
+            spot: float(inputs.spot),
+            strike: float(option.strike),
+            time: years_to_expiration(inputs.valuation_date, option.expiration),
+            rate: float(inputs.rate),
+            dividend_yield: float(inputs.dividend_yield),
+            volatility: float(inputs.volatility),
+        }
+    }
+
+    fn intrinsic(&self, spot: f64) -> f64 {
+        if self.call {
+            (spot - self.strike).max(0.0)
+        } else {
+            (self.strike - spot).max(0.0)
+        }
+    }
+
+    /// Value and Greeks once nothing is left but intrinsic value.
+    fn degenerate(&self) -> OptionValue {
+        let discount = (-self.rate * self.time).exp();
+        let carry = (-self.dividend_yield * self.time).exp();
+        let forward_intrinsic = if self.call {
+            self.spot * carry - self.strike * discount
+        } else {
+            self.strike * discount - self.spot * carry
+        };
+        let in_the_money = forward_intrinsic > 0.0;
+        let delta = match (in_the_money, self.call) {
+            (false, _) => 0.0,
+            (true, true) => carry,
+            (true, false) => -carry,
+        };
+        value(forward_intrinsic.max(0.0), delta, 0.0, 0.0, 0.0, 0.0)
+    }
+
+    /// European price only, for solvers.
+    pub fn black_scholes_price(&self) -> f64 {
+        if self.time <= 0.0 || self.volatility <= 0.0 {
+            return self.degenerate().price.to_f64().unwrap_or(0.0);
+        }
+        let (d1, d2) = self.d1_d2();
+        let discount = (-self.rate * self.time).exp();
+        let carry = (-self.dividend_yield * self.time).exp();
+        if self.call {
+            self.spot * carry * norm_cdf(d1) - self.strike * discount * norm_cdf(d2)
+        } else {
+            self.strike * discount * norm_cdf(-d2) - self.spot * carry * norm_cdf(-d1)
+        }
+    }
+
+    /// Sensitivity of the European price to a unit change in volatility.
+    pub fn black_scholes_vega(&self) -> f64 {
+        if self.time <= 0.0 || self.volatility <= 0.0 {
+            return 0.0;
+        }
+        let (d1, _) = self.d1_d2();
+        self.spot * (-self.dividend_yield * self.time).exp() * norm_pdf(d1) * self.time.sqrt()
+    }
+
+    fn d1_d2(&self) -> (f64, f64) {
+        let sd = self.volatility * self.time.sqrt();
+        let d1 = ((self.spot / self.strike).ln()
+            + (self.rate - self.dividend_yield + self.volatility * self.volatility / 2.0)
+                * self.time)
+            / sd;
+        (d1, d1 - sd)
+    }
+
+    pub fn black_scholes(&self) -> OptionValue {
+        if self.time <= 0.0 || self.volatility <= 0.0 {
+            return self.degenerate();
+        }
+        let (d1, d2) = self.d1_d2();
+        let sqrt_t = self.time.sqrt();
+        let discount = (-self.rate * self.time).exp();
+        let carry = (-self.dividend_yield * self.time).exp();
+        let density = norm_pdf(d1);
+
+        let gamma = carry * density / (self.spot * self.volatility * sqrt_t);
+        let vega = self.black_scholes_vega();
+        let decay = -self.spot * carry * density * self.volatility / (2.0 * sqrt_t);
+        let (delta, theta, rho) = if self.call {
+            (
+                carry * norm_cdf(d1),
+                decay - self.rate * self.strike * discount * norm_cdf(d2)
+                    + self.dividend_yield * self.spot * carry * norm_cdf(d1),
+                self.strike * self.time * discount * norm_cdf(d2),
+            )
+        } else {
+            (
+                -carry * norm_cdf(-d1),
+                decay + self.rate * self.strike * discount * norm_cdf(-d2)
+                    - self.dividend_yield * self.spot * carry * norm_cdf(-d1),
+                -self.strike * self.time * discount * norm_cdf(-d2),
+            )
+        };
+        value(
+            self.black_scholes_price(),
+            delta,
+            gamma,
+            theta / DAYS_PER_YEAR,
+            vega / 100.0,
+            rho / 100.0,
+        )
+    }
+
+    /// Price on a CRR tree, plus the option values at the first two steps for Greeks.
+    fn tree(&self, steps: usize) -> (f64, [f64; 2], [f64; 3]) {
+        let dt = self.time / steps as f64;
+        let up = (self.volatility * dt.sqrt()).exp();
+        let down = 1.0 / up;
+        let growth = ((self.rate - self.dividend_yield) * dt).exp();
+        let p = ((growth - down) / (up - down)).clamp(0.0, 1.0);
+        let discount = (-self.rate * dt).exp();
+
+        let mut values: Vec<f64> = (0..=steps)
+            .map(|i| self.intrinsic(self.spot * up.powi(i as i32) * down.powi((steps - i) as i32)))
+            .collect();
+        let mut step_one = [0.0; 2];
+        let mut step_two = [0.0; 3];
+        for step in (0..steps).rev() {
+            for i in 0..=step {
+                let spot = self.spot * up.powi(i as i32) * down.powi((step - i) as i32);
+                let held = discount * (p * values[i + 1] + (1.0 - p) * values[i]);
+                values[i] = held.max(self.intrinsic(spot));
+            }
+            match step {
+                2 => step_two.copy_from_slice(&values[..3]),
+                1 => step_one.copy_from_slice(&values[..2]),
+                _ => {}
+            }
+        }
+        (values[0], step_one, step_two)
+    }
+
+    /// American price only, for solvers.
+    pub fn american_price(&self, steps: usize) -> f64 {
+        if self.time <= 0.0 || self.volatility <= 0.0 {
+            // Exercising now is always an alternative to waiting
+            let forward = self.degenerate().price.to_f64().unwrap_or(0.0);
+            return forward.max(self.intrinsic(self.spot));
+        }
+        self.tree(steps.max(3)).0
+    }
+
+    pub fn american(&self, steps: usize) -> OptionValue {
+        if self.time <= 0.0 || self.volatility <= 0.0 {
+            // Exercising now can beat holding to expiration, e.g. a deep put
+            let held = self.degenerate();
+            let exercised = self.intrinsic(self.spot);
+            if held.price.to_f64().unwrap_or(0.0) >= exercised {
+                return held;
+            }
+            let delta = if self.call { 1.0 } else { -1.0 };
+            return value(exercised, delta, 0.0, 0.0, 0.0, 0.0);
+        }
+        let steps = steps.max(3);
+        let (price, step_one, step_two) = self.tree(steps);
+
+        let dt = self.time / steps as f64;
+        let up = (self.volatility * dt.sqrt()).exp();
+        let down = 1.0 / up;
+        let (s_down, s_up) = (self.spot * down, self.spot * up);
+        let delta = (step_one[1] - step_one[0]) / (s_up - s_down);
+        let (s_dd, s_ud, s_uu) = (self.spot * down * down, self.spot, self.spot * up * up);
+        let delta_up = (step_two[2] - step_two[1]) / (s_uu - s_ud);
+        let delta_down = (step_two[1] - step_two[0]) / (s_ud - s_dd);
+        let gamma = (delta_up - delta_down) / ((s_uu - s_dd) / 2.0);
+        let theta = (step_two[1] - price) / (2.0 * dt);
+
+        let bumped = |model: Model| model.tree(steps).0;
+        let vol_bump = 0.01;
+        let vega = (bumped(Model {
+            volatility: self.volatility + vol_bump,
+            ..*self
+        }) - bumped(Model {
+            volatility: (self.volatility - vol_bump).max(1e-6),
+            ..*self
+        })) / (self.volatility + vol_bump - (self.volatility - vol_bump).max(1e-6));
+        let rate_bump = 0.0001;
+        let rho = (bumped(Model {
+            rate: self.rate + rate_bump,
+            ..*self
+        }) - bumped(Model {
+            rate: self.rate - rate_bump,
+            ..*self
+        })) / (2.0 * rate_bump);
+
+        value(
+            price,
+            delta,
+            gamma,
+            theta / DAYS_PER_YEAR,
+            vega / 100.0,
+            rho / 100.0,
+        )
+    }
+}
+
+fn value(price: f64, delta: f64, gamma: f64, theta: f64, vega: f64, rho: f64) -> OptionValue {
+    OptionValue {
+        price: Decimal::from_f64(price)
+            .unwrap_or_default()
+            .round_dp(PRICE_SCALE),
+        delta,
+        gamma,
+        theta,
+        vega,
+        rho,
+    }
+}
+
+fn norm_pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// Standard normal CDF, accurate to double precision (Hart 1968, via West 2005).
+pub(crate) fn norm_cdf(x: f64) -> f64 {
+    let z = x.abs();
+    let tail = if z > 37.0 {
+        0.0
+    } else {
+        let e = (-z * z / 2.0).exp();
+        if z < 7.071_067_811_865_47 {
+            let mut n = 3.526_249_659_989_11e-2 * z + 0.700_383_064_443_688;
+            n = n * z + 6.373_962_203_531_65;
+            n = n * z + 33.912_866_078_383;
+            n = n * z + 112.079_291_497_871;
+            n = n * z + 221.213_596_169_931;
+            n = n * z + 220.206_867_912_376;
+            let mut d = 8.838_834_764_831_84e-2 * z + 1.755_667_163_182_64;
+            d = d * z + 16.064_177_579_207;
+            d = d * z + 86.780_732_202_946_1;
+            d = d * z + 296.564_248_779_674;
+            d = d * z + 637.333_633_378_831;
+            d = d * z + 793.826_512_519_948;
+            d = d * z + 440.413_735_824_752;
+            e * n / d
+        } else {
+            let mut d = z + 0.65;
+            d = z + 4.0 / d;
+            d = z + 3.0 / d;
+            d = z + 2.0 / d;
+            d = z + 1.0 / d;
+            e / d / 2.506_628_274_631
+        }
+    };
+    if x > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::{date, dec};
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    /// One year out (365 days), struck at 100
+    fn contract(option_type: OptionType) -> OptionSymbol {
+        OptionSymbol::new("XYZ", date(2025, 1, 1), option_type, Decimal::from(100))
+    }
+
+    fn inputs() -> PricingInputs {
+        PricingInputs::new(Decimal::from(100), dec("0.2"), date(2024, 1, 2)).rate(dec("0.05"))
+    }
+
+    #[test]
+    fn test_norm_cdf() {
+        assert_close(norm_cdf(0.0), 0.5, 1e-15);
+        assert_close(norm_cdf(1.96), 0.975_002_104_851_780, 1e-12);
+        assert_close(norm_cdf(-1.0), 0.158_655_253_931_457, 1e-12);
+        assert_close(norm_cdf(-8.0), 6.220_960_574_271_78e-16, 1e-20);
+        assert_eq!(norm_cdf(40.0), 1.0);
+    }
+
+    #[test]
+    fn test_black_scholes_reference_values() {
+        let call = black_scholes(&contract(OptionType::Call), &inputs());
+        assert_close(call.price.to_f64().unwrap(), 10.450_584, 1e-5);
+        assert_close(call.delta, 0.636_831, 1e-5);
+        assert_close(call.gamma, 0.018_762, 1e-5);
+        assert_close(call.vega, 0.375_240, 1e-5);
+        assert_close(call.theta, -6.414_028 / 365.0, 1e-6);
+        assert_close(call.rho, 0.532_325, 1e-5);
+
+        let put = black_scholes(&contract(OptionType::Put), &inputs());
+        assert_close(put.price.to_f64().unwrap(), 5.573_526, 1e-5);
+        assert_close(put.delta, -0.363_169, 1e-5);
+        assert_close(put.gamma, call.gamma, 1e-12);
+
+        // Put-call parity: C - P = S - K e^(-rT)
+        let parity = 100.0 - 100.0 * (-0.05_f64).exp();
+        assert_close((call.price - put.price).to_f64().unwrap(), parity, 1e-5);
+    }
+
+    #[test]
+    fn test_american_exercise_premium() {
+        let put = contract(OptionType::Put);
+        let european = black_scholes(&put, &inputs());
+        let american_put = american(&put, &inputs());
+        // Early exercise adds value to the put (reference about 6.09)
+        assert!(american_put.price > european.price);
+        assert_close(american_put.price.to_f64().unwrap(), 6.09, 0.02);
+        assert_close(american_put.delta, -0.41, 0.02);
+        assert_close(american_put.gamma, 0.0226, 0.002);
+        assert!(american_put.theta < 0.0);
+        assert!(american_put.vega > 0.0);
+        assert!(american_put.rho < 0.0);
+
+        // Without dividends an American call is worth the European call
+        let call = contract(OptionType::Call);
+        let american_call = price_option(&call, &inputs(), ExerciseStyle::American);
+        let european_call = price_option(&call, &inputs(), ExerciseStyle::European);
+        assert_close(
+            (american_call.price - european_call.price)
+                .to_f64()
+                .unwrap(),
+            0.0,
+            0.02,
+        );
+        assert_close(american_call.delta, european_call.delta, 0.01);
+        assert_close(american_call.vega, european_call.vega, 0.005);
+        assert_close(american_call.rho, european_call.rho, 0.01);
+    }
+
+    #[test]
+    fn test_expired_and_scenarios() {
+        let call = contract(OptionType::Call);
+        let at_expiration = inputs().on(date(2025, 1, 1)).with_spot(Decimal::from(112));
+        let expired = black_scholes(&call, &at_expiration);
+        assert_eq!(expired.price, Decimal::from(12));
+        assert_eq!(expired.delta, 1.0);
+        assert_eq!(expired.gamma, 0.0);
+        assert_eq!(
+            american(&call, &at_expiration.with_spot(Decimal::from(90))).price,
+            Decimal::ZERO
+        );
+
+        // Higher spot, higher call value; later date, less time value
+        let base = black_scholes(&call, &inputs());
+        let up = black_scholes(&call, &inputs().with_spot(Decimal::from(105)));
+        let later = black_scholes(&call, &inputs().on(date(2024, 7, 1)));
+        assert!(up.price > base.price);
+        assert!(later.price < base.price);
+
+        // Dividends lower the call value
+        let with_yield = black_scholes(&call, &inputs().dividend_yield(dec("0.03")));
+        assert!(with_yield.price < base.price);
+
+        // Without volatility a deep put is still worth exercising today
+        let put = contract(OptionType::Put);
+        let flat = PricingInputs::new(Decimal::from(50), Decimal::ZERO, date(2024, 1, 2))
+            .rate(dec("0.05"));
+        let american_put = american(&put, &flat);
+        assert_eq!(american_put.price, Decimal::from(50));
+        assert_eq!(american_put.delta, -1.0);
+        assert!(black_scholes(&put, &flat).price < american_put.price);
+    }
+}
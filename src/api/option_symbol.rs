@@ -0,0 +1,316 @@
+//! Structured equity option symbols.
+//!
+//! [`OptionSymbol`] parses and formats the padded OCC form used for trading
+//! (`AAPL  240119C00150000`) and the DxFeed form used for streaming
+//! (`.AAPL240119C150`).
+//!
+//! # Example
+//! ```ignore
+//! let option: OptionSymbol = "AAPL  240119C00150000".parse()?;
+//! assert_eq!(option.strike, Decimal::from(150));
+//! assert_eq!(option.to_streamer_symbol().0, ".AAPL240119C150");
+//! let info = client.get_equity_option(&option).await?;
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::instrument::OptionType;
+use super::order::{AsSymbol, Symbol};
+use super::quote_streaming::DxFeedSymbol;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SymbolParseError {
+    #[error("{0:?} is not an OCC option symbol")]
+    InvalidOcc(String),
+    #[error("{0:?} is not a DxFeed option symbol")]
+    InvalidStreamer(String),
+    #[error("{0:?} is not a futures symbol")]
+    InvalidFuture(String),
+    #[error("{0:?} is not a futures option symbol")]
+    InvalidFutureOption(String),
+}
+
+/// Width of the space-padded root in an OCC symbol
+const OCC_ROOT_WIDTH: usize = 6;
+
+/// An equity or index option contract.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OptionSymbol {
+    /// Option root, which may differ from the underlying (e.g., "SPXW")
+    pub root: String,
+    pub expiration: NaiveDate,
+    pub option_type: OptionType,
+    pub strike: Decimal,
+}
+
+impl OptionSymbol {
+    pub fn new(
+        root: impl Into<String>,
+        expiration: NaiveDate,
+        option_type: OptionType,
+        strike: Decimal,
+    ) -> Self {
+        Self {
+            root: root.into(),
+            expiration,
+            option_type,
+            strike: strike.normalize(),
+        }
+    }
+
+    /// Parse the OCC form, e.g. `AAPL  240119C00150000`.
+    ///
+    /// The root padding is not required, so `AAPL240119C00150000` also parses.
+    pub fn parse_occ(symbol: &str) -> Result<Self, SymbolParseError> {
+        let invalid = || SymbolParseError::InvalidOcc(symbol.to_string());
+        let trimmed = symbol.trim_end();
+        // yymmdd + C/P + 8 strike digits
+        if !trimmed.is_ascii() || trimmed.len() < 16 {
+            return Err(invalid());
+        }
+        let (root, tail) = trimmed.split_at(trimmed.len() - 15);
+        let root = root.trim();
+        if root.is_empty() || root.len() > OCC_ROOT_WIDTH || root.contains(' ') {
+            return Err(invalid());
+        }
+        let expiration = parse_date(&tail[..6]).ok_or_else(invalid)?;
+        let option_type = parse_option_type(&tail[6..7]).ok_or_else(invalid)?;
+        let digits = &tail[7..];
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let strike = digits.parse::<i64>().map_err(|_| invalid())?;
+        Ok(Self::new(
+            root,
+            expiration,
+            option_type,
+            Decimal::new(strike, 3),
+        ))
+    }
+
+    /// Parse the DxFeed streamer form, e.g. `.AAPL240119C150` or `.SPXW240119P4512.5`.
+    pub fn parse_streamer(symbol: &str) -> Result<Self, SymbolParseError> {
+        let invalid = || SymbolParseError::InvalidStreamer(symbol.to_string());
+        let body = symbol.strip_prefix('.').ok_or_else(invalid)?;
+        if !body.is_ascii() {
+            return Err(invalid());
+        }
+        // The strike is the trailing run of digits and dots; the option type precedes it.
+        let strike_start = body
+            .rfind(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .ok_or_else(invalid)?;
+        let strike = Decimal::from_str(&body[strike_start + 1..]).map_err(|_| invalid())?;
+        let option_type =
+            parse_option_type(&body[strike_start..strike_start + 1]).ok_or_else(invalid)?;
+        if strike_start < 7 {
+            return Err(invalid());
+        }
+        let expiration = parse_date(&body[strike_start - 6..strike_start]).ok_or_else(invalid)?;
+        let root = &body[..strike_start - 6];
+        if root.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self::new(root, expiration, option_type, strike))
+    }
+
+    /// Format as a padded OCC symbol.
+    pub fn to_occ(&self) -> String {
+        let strike = (self.strike * Decimal::from(1000)).trunc();
+        format!(
+            "{:<width$}{}{}{:08}",
+            self.root,
+            self.expiration.format("%y%m%d"),
+            option_type_code(self.option_type),
+            strike,
+            width = OCC_ROOT_WIDTH
+        )
+    }
+
+    /// Format as a DxFeed streamer symbol.
+    pub fn to_streamer_symbol(&self) -> DxFeedSymbol {
+        DxFeedSymbol(format!(
+            ".{}{}{}{}",
+            self.root,
+            self.expiration.format("%y%m%d"),
+            option_type_code(self.option_type),
+            self.strike.normalize()
+        ))
+    }
+
+    pub fn is_call(&self) -> bool {
+        self.option_type == OptionType::Call
+    }
+}
+
+fn parse_date(yymmdd: &str) -> Option<NaiveDate> {
+    if !yymmdd.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    NaiveDate::parse_from_str(yymmdd, "%y%m%d").ok()
+}
+
+fn parse_option_type(code: &str) -> Option<OptionType> {
+    match code {
+        "C" => Some(OptionType::Call),
+        "P" => Some(OptionType::Put),
+        _ => None,
+    }
+}
+
+fn option_type_code(option_type: OptionType) -> char {
+    match option_type {
+        OptionType::Call => 'C',
+        OptionType::Put => 'P',
+    }
+}
+
+impl fmt::Display for OptionSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_occ())
+    }
+}
+
+impl FromStr for OptionSymbol {
+    type Err = SymbolParseError;
+
+    /// Accepts either the OCC or the DxFeed form.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with('.') {
+            Self::parse_streamer(s)
+        } else {
+            Self::parse_occ(s)
+        }
+    }
+}
+
+impl AsSymbol for OptionSymbol {
+    fn as_symbol(&self) -> Symbol {
+        Symbol(self.to_occ())
+    }
+}
+
+impl AsSymbol for &OptionSymbol {
+    fn as_symbol(&self) -> Symbol {
+        (*self).as_symbol()
+    }
+}
+
+impl From<&OptionSymbol> for DxFeedSymbol {
+    fn from(option: &OptionSymbol) -> Self {
+        option.to_streamer_symbol()
+    }
+}
+
+impl TryFrom<&Symbol> for OptionSymbol {
+    type Error = SymbolParseError;
+
+    fn try_from(symbol: &Symbol) -> Result<Self, Self::Error> {
+        Self::parse_occ(&symbol.0)
+    }
+}
+
+impl TryFrom<&DxFeedSymbol> for OptionSymbol {
+    type Error = SymbolParseError;
+
+    fn try_from(symbol: &DxFeedSymbol) -> Result<Self, Self::Error> {
+        Self::parse_streamer(&symbol.0)
+    }
+}
+
+impl Symbol {
+    /// Parse this symbol as an OCC option symbol.
+    ///
+    /// Works for `Strike::call`, `LiveOrderLeg::symbol`, `FullPosition::symbol`
+    /// and anywhere else option symbols appear.
+    pub fn to_option_symbol(&self) -> Result<OptionSymbol, SymbolParseError> {
+        OptionSymbol::try_from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::date;
+
+    #[test]
+    fn test_parse_occ() {
+        let option = OptionSymbol::parse_occ("AAPL  240119C00150000").unwrap();
+        assert_eq!(option.root, "AAPL");
+        assert_eq!(option.expiration, date(2024, 1, 19));
+        assert_eq!(option.option_type, OptionType::Call);
+        assert_eq!(option.strike, Decimal::from(150));
+
+        let option = OptionSymbol::parse_occ("SPXW  240119P04512500").unwrap();
+        assert_eq!(option.root, "SPXW");
+        assert!(!option.is_call());
+        assert_eq!(option.strike, Decimal::from_str("4512.5").unwrap());
+
+        let unpadded = OptionSymbol::parse_occ("AAPL240119C00150000").unwrap();
+        assert_eq!(unpadded.to_occ(), "AAPL  240119C00150000");
+    }
+
+    #[test]
+    fn test_parse_occ_rejects_malformed() {
+        for bad in [
+            "AAPL",
+            "/ESZ4",
+            "AAPL  241319C00150000",
+            "AAPL  240119X00150000",
+            "AAPL  240119C0015000A",
+            "TOOLONGR240119C00150000",
+            "               ",
+        ] {
+            assert!(
+                matches!(
+                    OptionSymbol::parse_occ(bad),
+                    Err(SymbolParseError::InvalidOcc(_))
+                ),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_occ_round_trip() {
+        for occ in [
+            "AAPL  240119C00150000",
+            "SPXW  240119P04512500",
+            "BRKB  250620C00425000",
+            "F     240216P00012500",
+        ] {
+            assert_eq!(OptionSymbol::parse_occ(occ).unwrap().to_occ(), occ);
+        }
+    }
+
+    #[test]
+    fn test_streamer_conversion() {
+        let option = OptionSymbol::parse_occ("AAPL  240119C00150000").unwrap();
+        assert_eq!(option.to_streamer_symbol().0, ".AAPL240119C150");
+
+        let option = OptionSymbol::parse_occ("SPXW  240119P04512500").unwrap();
+        let streamer = DxFeedSymbol::from(&option);
+        assert_eq!(streamer.0, ".SPXW240119P4512.5");
+        assert_eq!(OptionSymbol::try_from(&streamer).unwrap(), option);
+
+        assert!(OptionSymbol::parse_streamer("AAPL240119C150").is_err());
+        assert!(OptionSymbol::parse_streamer(".AAPL").is_err());
+        assert!(OptionSymbol::parse_streamer(".240119C150").is_err());
+    }
+
+    #[test]
+    fn test_from_str_as_symbol_and_symbol_helper() {
+        let from_occ: OptionSymbol = "QQQ   240315P00400000".parse().unwrap();
+        let from_streamer: OptionSymbol = ".QQQ240315P400".parse().unwrap();
+        assert_eq!(from_occ, from_streamer);
+        assert_eq!(from_occ.to_string(), "QQQ   240315P00400000");
+        assert_eq!(from_occ.as_symbol(), Symbol::from("QQQ   240315P00400000"));
+
+        let symbol = Symbol::from("QQQ   240315P00400000");
+        assert_eq!(symbol.to_option_symbol().unwrap(), from_occ);
+        assert!(Symbol::from("QQQ").to_option_symbol().is_err());
+    }
+}
@@ -1,17 +1,18 @@
+use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::api::accounts::AccountNumber;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum PriceEffect {
     Debit,
     Credit,
     None,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum Action {
     #[serde(rename = "Buy to Open")]
     BuyToOpen,
@@ -36,9 +37,19 @@ impl Action {
             Action::Buy => "Buy",
         }
     }
+
+    /// Whether this action can only reduce an existing position.
+    pub fn is_closing(&self) -> bool {
+        matches!(self, Action::BuyToClose | Action::SellToClose)
+    }
+
+    /// Whether this action buys (as opposed to sells).
+    pub fn is_buy(&self) -> bool {
+        matches!(self, Action::Buy | Action::BuyToOpen | Action::BuyToClose)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub enum InstrumentType {
     Equity,
     #[serde(rename = "Equity Option")]
@@ -73,7 +84,7 @@ impl InstrumentType {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum OrderType {
     Limit,
     Market,
@@ -86,7 +97,7 @@ pub enum OrderType {
     NotionalMarket,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum TimeInForce {
     Day,
     GTC,
@@ -97,7 +108,7 @@ pub enum TimeInForce {
     IOC,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum OrderStatus {
     Received,
     Routed,
@@ -118,6 +129,26 @@ pub enum OrderStatus {
     PartiallyRemoved,
 }
 
+impl OrderStatus {
+    /// Whether the order has reached a final state and will not change again.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Filled
+                | OrderStatus::Cancelled
+                | OrderStatus::Expired
+                | OrderStatus::Rejected
+                | OrderStatus::Removed
+                | OrderStatus::PartiallyRemoved
+        )
+    }
+
+    /// Whether the order is still working at the exchange or broker.
+    pub fn is_working(&self) -> bool {
+        !self.is_terminal()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(transparent)]
 pub struct Symbol(pub String);
@@ -128,6 +159,14 @@ impl<T: AsRef<str>> From<T> for Symbol {
     }
 }
 
+impl Symbol {
+    /// The root of an OCC option symbol (`"AAPL  240119C00150000"` -> `"AAPL"`),
+    /// or the symbol itself.
+    pub fn root(&self) -> Symbol {
+        Symbol::from(self.0.split_whitespace().next().unwrap_or(&self.0))
+    }
+}
+
 pub trait AsSymbol {
     fn as_symbol(&self) -> Symbol;
 }
@@ -150,7 +189,7 @@ impl AsSymbol for &Symbol {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(transparent)]
 pub struct OrderId(pub u64);
 
@@ -170,31 +209,76 @@ pub struct LiveOrderRecord {
     pub cancellable: bool,
     pub editable: bool,
     pub edited: bool,
+    #[serde(default)]
+    pub received_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub legs: Vec<LiveOrderLeg>,
 }
 
-#[derive(Debug, Deserialize)]
+impl LiveOrderRecord {
+    /// Time since the order was received, if the API reported when that was.
+    pub fn age(&self) -> Option<chrono::Duration> {
+        self.received_at.map(|at| Utc::now() - at)
+    }
+
+    /// Whether any leg is of the given instrument type.
+    pub fn has_instrument_type(&self, instrument_type: &InstrumentType) -> bool {
+        self.legs
+            .iter()
+            .any(|leg| &leg.instrument_type == instrument_type)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct LiveOrderLeg {
     pub instrument_type: InstrumentType,
     pub symbol: Symbol,
-    pub quantity: u64,
-    pub remaining_quantity: u64,
+    /// Fractional for crypto orders
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub quantity: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub remaining_quantity: Decimal,
     pub action: Action,
-    pub fills: Vec<String>,
+    #[serde(default)]
+    pub fills: Vec<LiveOrderFill>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct LiveOrderFill {
+    pub fill_id: Option<String>,
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub quantity: Option<Decimal>,
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub fill_price: Option<Decimal>,
+    pub filled_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Builder, Serialize)]
+#[derive(Builder, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 #[builder(setter(into, strip_option))]
 pub struct Order {
-    time_in_force: TimeInForce,
-    order_type: OrderType,
+    pub(crate) time_in_force: TimeInForce,
+    pub(crate) order_type: OrderType,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub(crate) price: Option<Decimal>,
+    pub(crate) price_effect: PriceEffect,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[builder(default)]
-    price: Option<Decimal>,
-    price_effect: PriceEffect,
-    legs: Vec<OrderLeg>,
+    pub(crate) stop_trigger: Option<Decimal>,
+
+    /// Dollar amount for `Notional Market` orders, which size by value instead of quantity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub(crate) value: Option<Decimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub(crate) value_effect: Option<PriceEffect>,
+    pub(crate) legs: Vec<OrderLeg>,
 }
 
 impl Default for Order {
@@ -204,20 +288,94 @@ impl Default for Order {
             order_type: OrderType::Market,
             price: None,
             price_effect: PriceEffect::None,
+            stop_trigger: None,
+            value: None,
+            value_effect: None,
             legs: Vec::new(),
         }
     }
 }
 
-#[derive(Builder, Serialize, Deserialize, Clone, Debug)]
+impl Order {
+    pub fn time_in_force(&self) -> &TimeInForce {
+        &self.time_in_force
+    }
+
+    pub fn order_type(&self) -> &OrderType {
+        &self.order_type
+    }
+
+    pub fn price(&self) -> Option<Decimal> {
+        self.price
+    }
+
+    pub fn price_effect(&self) -> &PriceEffect {
+        &self.price_effect
+    }
+
+    pub fn stop_trigger(&self) -> Option<Decimal> {
+        self.stop_trigger
+    }
+
+    pub fn value(&self) -> Option<Decimal> {
+        self.value
+    }
+
+    pub fn value_effect(&self) -> Option<&PriceEffect> {
+        self.value_effect.as_ref()
+    }
+
+    pub fn legs(&self) -> &[OrderLeg] {
+        &self.legs
+    }
+}
+
+#[derive(Builder, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
-#[builder(setter(into))]
+#[builder(setter(into), build_fn(validate = "Self::validate"))]
 pub struct OrderLeg {
-    instrument_type: InstrumentType,
-    symbol: Symbol,
-    #[serde(with = "rust_decimal::serde::float")]
-    quantity: Decimal,
-    action: Action,
+    pub(crate) instrument_type: InstrumentType,
+    pub(crate) symbol: Symbol,
+    /// Serialized exactly so fractional crypto quantities keep their precision.
+    /// Zero only for the leg of a notional order (see [`Order::crypto_notional`]),
+    /// where the quantity is omitted and the order's `value` sizes the trade
+    /// instead; the builder rejects a zero quantity.
+    #[serde(
+        default,
+        with = "rust_decimal::serde::arbitrary_precision",
+        skip_serializing_if = "Decimal::is_zero"
+    )]
+    pub(crate) quantity: Decimal,
+    pub(crate) action: Action,
+}
+
+impl OrderLegBuilder {
+    fn validate(&self) -> std::result::Result<(), String> {
+        match self.quantity {
+            Some(quantity) if quantity.is_zero() => {
+                Err("order leg quantity must not be zero".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl OrderLeg {
+    pub fn instrument_type(&self) -> &InstrumentType {
+        &self.instrument_type
+    }
+
+    pub fn symbol(&self) -> &Symbol {
+        &self.symbol
+    }
+
+    pub fn quantity(&self) -> Decimal {
+        self.quantity
+    }
+
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -576,6 +734,52 @@ mod tests {
         assert_eq!(price_val, Decimal::from_str("123.456789").unwrap());
     }
 
+    #[test]
+    fn test_order_json_round_trip() {
+        let json = json!({
+            "time-in-force": "GTC",
+            "order-type": "Limit",
+            "price": "1.85",
+            "price-effect": "Credit",
+            "legs": [
+                {
+                    "instrument-type": "Equity Option",
+                    "symbol": "SPY   240119P00450000",
+                    "quantity": 2.0,
+                    "action": "Buy to Close"
+                },
+                {
+                    "instrument-type": "Equity Option",
+                    "symbol": "SPY   240216P00450000",
+                    "quantity": 2.0,
+                    "action": "Sell to Open"
+                }
+            ]
+        });
+
+        let order: Order = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(order.time_in_force(), &TimeInForce::GTC);
+        assert_eq!(order.order_type(), &OrderType::Limit);
+        assert_eq!(order.price(), Some(Decimal::from_str("1.85").unwrap()));
+        assert_eq!(order.price_effect(), &PriceEffect::Credit);
+        assert_eq!(order.stop_trigger(), None);
+        assert_eq!(order.legs().len(), 2);
+        assert_eq!(order.legs()[1].symbol().0, "SPY   240216P00450000");
+        assert_eq!(order.legs()[1].quantity(), Decimal::from(2));
+        assert_eq!(order.legs()[1].action(), &Action::SellToOpen);
+        assert_eq!(
+            order.legs()[1].instrument_type(),
+            &InstrumentType::EquityOption
+        );
+
+        assert_eq!(serde_json::to_value(&order).unwrap(), json);
+
+        let copy = order.clone();
+        assert_eq!(copy, order);
+        let reparsed: Order = serde_json::from_str(&serde_json::to_string(&copy).unwrap()).unwrap();
+        assert_eq!(reparsed, order);
+    }
+
     #[test]
     fn test_live_order_record_deserialization() {
         let json = json!({
@@ -608,6 +812,22 @@ mod tests {
         assert!(!record.edited);
     }
 
+    #[test]
+    fn test_live_order_leg_fractional_quantity() {
+        let json = json!({
+            "instrument-type": "Cryptocurrency",
+            "symbol": "BTC/USD",
+            "quantity": 0.5,
+            "remaining-quantity": "0.25",
+            "action": "Buy to Open"
+        });
+
+        let leg: LiveOrderLeg = serde_json::from_value(json).unwrap();
+        assert_eq!(leg.quantity, Decimal::from_str("0.5").unwrap());
+        assert_eq!(leg.remaining_quantity, Decimal::from_str("0.25").unwrap());
+        assert!(leg.fills.is_empty());
+    }
+
     #[test]
     fn test_dry_run_record_deserialization() {
         let json = json!({
@@ -743,6 +963,17 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_order_leg_builder_rejects_zero_quantity() {
+        let leg = OrderLegBuilder::default()
+            .instrument_type(InstrumentType::Equity)
+            .symbol(Symbol::from("AAPL"))
+            .quantity(Decimal::ZERO)
+            .action(Action::Buy)
+            .build();
+        assert!(leg.is_err());
+    }
+
     #[test]
     #[should_panic]
     fn test_order_leg_builder_missing_required_fields() {
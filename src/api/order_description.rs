@@ -0,0 +1,647 @@
+//! Human-readable order summaries.
+//!
+//! [`Order`], [`DryRunRecord`] and [`LiveOrderRecord`] implement `Display`
+//! with a one-line trader-style description, e.g.
+//! `Sell 1 AAPL 19Jan24 150/155 Call Vertical @ 1.25 cr, Day`.
+//! Common option strategies are recognized from the legs; anything else is
+//! listed leg by leg.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::option_symbol::OptionSymbol;
+use super::order::{
+    Action, DryRunRecord, InstrumentType, LiveOrderRecord, Order, OrderLeg, OrderType, PriceEffect,
+    Symbol, TimeInForce,
+};
+
+struct LegView<'a> {
+    instrument_type: &'a InstrumentType,
+    symbol: &'a Symbol,
+    quantity: Decimal,
+    action: &'a Action,
+}
+
+impl<'a> From<&'a OrderLeg> for LegView<'a> {
+    fn from(leg: &'a OrderLeg) -> Self {
+        Self {
+            instrument_type: &leg.instrument_type,
+            symbol: &leg.symbol,
+            quantity: leg.quantity.abs(),
+            action: &leg.action,
+        }
+    }
+}
+
+struct OptionLeg<'a> {
+    occ: OptionSymbol,
+    quantity: Decimal,
+    action: &'a Action,
+}
+
+fn side(action: &Action) -> &'static str {
+    if action.is_buy() {
+        "Buy"
+    } else {
+        "Sell"
+    }
+}
+
+fn fmt_date(date: NaiveDate) -> String {
+    date.format("%d%b%y").to_string()
+}
+
+fn fmt_price(price: Decimal) -> String {
+    if price.scale() <= 2 {
+        format!("{:.2}", price)
+    } else {
+        price.normalize().to_string()
+    }
+}
+
+fn fmt_strikes(strikes: &[Decimal]) -> String {
+    strikes
+        .iter()
+        .map(|s| s.normalize().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn option_type(is_call: bool) -> &'static str {
+    if is_call {
+        "Call"
+    } else {
+        "Put"
+    }
+}
+
+fn time_in_force(tif: &TimeInForce) -> &'static str {
+    match tif {
+        TimeInForce::Day => "Day",
+        TimeInForce::GTC => "GTC",
+        TimeInForce::GTD => "GTD",
+        TimeInForce::Ext => "Ext",
+        TimeInForce::GTCExt => "GTC Ext",
+        TimeInForce::IOC => "IOC",
+    }
+}
+
+/// Side of the whole order: credit orders sell the structure, debit orders buy it.
+fn order_side(price_effect: &PriceEffect, first: &Action) -> &'static str {
+    match price_effect {
+        PriceEffect::Credit => "Sell",
+        PriceEffect::Debit => "Buy",
+        PriceEffect::None => side(first),
+    }
+}
+
+/// Name a recognized multi-leg option strategy, returning the structure text
+/// (without side or quantity) and the number of units.
+fn describe_strategy(legs: &[OptionLeg]) -> Option<(String, Decimal)> {
+    let units = legs.iter().map(|l| l.quantity).min()?;
+    let root = &legs[0].occ.root;
+    if legs.iter().any(|l| &l.occ.root != root) {
+        return None;
+    }
+    let same_expiration = legs
+        .iter()
+        .all(|l| l.occ.expiration == legs[0].occ.expiration);
+    let all_same_qty = legs.iter().all(|l| l.quantity == units);
+    let date = fmt_date(legs[0].occ.expiration);
+
+    match legs {
+        [a, b] if all_same_qty => {
+            let opposite = a.action.is_buy() != b.action.is_buy();
+            let same_type = a.occ.is_call() == b.occ.is_call();
+            let mut strikes = [a.occ.strike, b.occ.strike];
+            strikes.sort();
+
+            if same_type && opposite && same_expiration && a.occ.strike != b.occ.strike {
+                let kind = option_type(a.occ.is_call());
+                return Some((
+                    format!("{root} {date} {} {kind} Vertical", fmt_strikes(&strikes)),
+                    units,
+                ));
+            }
+            if same_type && opposite && !same_expiration {
+                let (near, far) = if a.occ.expiration < b.occ.expiration {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+                let dates = format!(
+                    "{}/{}",
+                    fmt_date(near.occ.expiration),
+                    fmt_date(far.occ.expiration)
+                );
+                let kind = option_type(a.occ.is_call());
+                if a.occ.strike == b.occ.strike {
+                    return Some((
+                        format!(
+                            "{root} {dates} {} {kind} Calendar",
+                            fmt_strikes(&[a.occ.strike])
+                        ),
+                        units,
+                    ));
+                }
+                return Some((
+                    format!(
+                        "{root} {dates} {} {kind} Diagonal",
+                        fmt_strikes(&[near.occ.strike, far.occ.strike])
+                    ),
+                    units,
+                ));
+            }
+            if !same_type && !opposite && same_expiration {
+                if a.occ.strike == b.occ.strike {
+                    return Some((
+                        format!("{root} {date} {} Straddle", fmt_strikes(&[a.occ.strike])),
+                        units,
+                    ));
+                }
+                return Some((
+                    format!("{root} {date} {} Strangle", fmt_strikes(&strikes)),
+                    units,
+                ));
+            }
+            None
+        }
+        [_, _, _] if same_expiration => {
+            // Butterfly: one type, 1:2:1 ratio, wings on one side and body on the other
+            let is_call = legs[0].occ.is_call();
+            if legs.iter().any(|l| l.occ.is_call() != is_call) {
+                return None;
+            }
+            let mut sorted: Vec<&OptionLeg> = legs.iter().collect();
+            sorted.sort_by_key(|l| l.occ.strike);
+            let (low, body, high) = (sorted[0], sorted[1], sorted[2]);
+            let wing = low.quantity;
+            let symmetric = high.quantity == wing
+                && body.quantity == wing * Decimal::TWO
+                && low.action.is_buy() == high.action.is_buy()
+                && body.action.is_buy() != low.action.is_buy()
+                && body.occ.strike - low.occ.strike == high.occ.strike - body.occ.strike;
+            symmetric.then(|| {
+                (
+                    format!(
+                        "{root} {date} {} {} Butterfly",
+                        fmt_strikes(&[low.occ.strike, body.occ.strike, high.occ.strike]),
+                        option_type(is_call)
+                    ),
+                    wing,
+                )
+            })
+        }
+        [_, _, _, _] if same_expiration && all_same_qty => {
+            let mut puts: Vec<&OptionLeg> = legs.iter().filter(|l| !l.occ.is_call()).collect();
+            let mut calls: Vec<&OptionLeg> = legs.iter().filter(|l| l.occ.is_call()).collect();
+            if puts.len() != 2 || calls.len() != 2 {
+                return None;
+            }
+            puts.sort_by_key(|l| l.occ.strike);
+            calls.sort_by_key(|l| l.occ.strike);
+            // Both wings long and both inner legs short (or the reverse)
+            let inner_short = !puts[1].action.is_buy() && !calls[0].action.is_buy();
+            let outer_long = puts[0].action.is_buy() && calls[1].action.is_buy();
+            let inner_long = puts[1].action.is_buy() && calls[0].action.is_buy();
+            let outer_short = !puts[0].action.is_buy() && !calls[1].action.is_buy();
+            if !((inner_short && outer_long) || (inner_long && outer_short)) {
+                return None;
+            }
+            if puts[1].occ.strike > calls[0].occ.strike {
+                return None;
+            }
+            if puts[1].occ.strike == calls[0].occ.strike {
+                let strikes = [puts[0].occ.strike, puts[1].occ.strike, calls[1].occ.strike];
+                return Some((
+                    format!("{root} {date} {} Iron Butterfly", fmt_strikes(&strikes)),
+                    units,
+                ));
+            }
+            let strikes = [
+                puts[0].occ.strike,
+                puts[1].occ.strike,
+                calls[0].occ.strike,
+                calls[1].occ.strike,
+            ];
+            Some((
+                format!("{root} {date} {} Iron Condor", fmt_strikes(&strikes)),
+                units,
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn describe_leg(leg: &LegView) -> String {
+    let quantity = leg.quantity.normalize();
+    match OptionSymbol::parse_occ(&leg.symbol.0).ok() {
+        Some(occ) if matches!(leg.instrument_type, InstrumentType::EquityOption) => format!(
+            "{} {} {} {} {} {}",
+            side(leg.action),
+            quantity,
+            occ.root,
+            fmt_date(occ.expiration),
+            occ.strike,
+            option_type(occ.is_call())
+        ),
+        // Notional legs carry no quantity
+        _ if quantity.is_zero() => format!("{} {}", side(leg.action), leg.symbol.0),
+        _ => format!("{} {} {}", side(leg.action), quantity, leg.symbol.0),
+    }
+}
+
+fn describe_legs(legs: &[LegView], price_effect: &PriceEffect) -> String {
+    let Some(first) = legs.first() else {
+        return "Empty order".to_string();
+    };
+    if legs.len() == 1 {
+        return describe_leg(first);
+    }
+
+    let options: Option<Vec<OptionLeg>> = legs
+        .iter()
+        .map(|leg| {
+            if !matches!(leg.instrument_type, InstrumentType::EquityOption) {
+                return None;
+            }
+            Some(OptionLeg {
+                occ: OptionSymbol::parse_occ(&leg.symbol.0).ok()?,
+                quantity: leg.quantity,
+                action: leg.action,
+            })
+        })
+        .collect();
+    if let Some((structure, units)) = options.as_deref().and_then(describe_strategy) {
+        return format!(
+            "{} {} {}",
+            order_side(price_effect, first.action),
+            units.normalize(),
+            structure
+        );
+    }
+
+    // Covered call / protective put style stock-and-option pairs, named only
+    // when the shares exactly cover the contracts
+    if let [a, b] = legs {
+        let (stock, option) = if matches!(a.instrument_type, InstrumentType::Equity) {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        if matches!(stock.instrument_type, InstrumentType::Equity)
+            && matches!(option.instrument_type, InstrumentType::EquityOption)
+        {
+            let covered = |occ: &OptionSymbol| {
+                stock.symbol.0 == occ.root
+                    && stock.quantity.abs() == option.quantity.abs() * Decimal::ONE_HUNDRED
+            };
+            if let Some(occ) = OptionSymbol::parse_occ(&option.symbol.0)
+                .ok()
+                .filter(covered)
+            {
+                let name = match (stock.action.is_buy(), option.action.is_buy(), occ.is_call()) {
+                    (true, false, true) => Some("Covered Call"),
+                    (true, true, false) => Some("Protective Put"),
+                    _ => None,
+                };
+                if let Some(name) = name {
+                    return format!(
+                        "{} {} {} {} {} {}",
+                        order_side(price_effect, stock.action),
+                        option.quantity.abs().normalize(),
+                        occ.root,
+                        fmt_date(occ.expiration),
+                        occ.strike,
+                        name
+                    );
+                }
+            }
+        }
+    }
+
+    legs.iter()
+        .map(describe_leg)
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+fn describe_terms(
+    f: &mut fmt::Formatter<'_>,
+    order_type: &OrderType,
+    price: Option<Decimal>,
+    price_effect: &PriceEffect,
+    stop_trigger: Option<Decimal>,
+    tif: &TimeInForce,
+) -> fmt::Result {
+    let effect = match price_effect {
+        PriceEffect::Credit => " cr",
+        PriceEffect::Debit => " db",
+        PriceEffect::None => "",
+    };
+    match (order_type, price) {
+        (OrderType::Market | OrderType::NotionalMarket, _) | (OrderType::Stop, _) | (_, None) => {
+            write!(f, " @ MKT")?
+        }
+        (_, Some(price)) => write!(f, " @ {}{}", fmt_price(price.abs()), effect)?,
+    }
+    if let Some(trigger) = stop_trigger {
+        write!(f, ", stop {}", fmt_price(trigger))?;
+    }
+    write!(f, ", {}", time_in_force(tif))
+}
+
+impl fmt::Display for Order {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let legs: Vec<LegView> = self.legs.iter().map(LegView::from).collect();
+        write!(f, "{}", describe_legs(&legs, &self.price_effect))?;
+        if let Some(value) = self.value {
+            write!(f, " ${} notional", fmt_price(value))?;
+        }
+        describe_terms(
+            f,
+            &self.order_type,
+            self.price,
+            &self.price_effect,
+            self.stop_trigger,
+            &self.time_in_force,
+        )
+    }
+}
+
+impl fmt::Display for DryRunRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let legs: Vec<LegView> = self.legs.iter().map(LegView::from).collect();
+        write!(f, "{}", describe_legs(&legs, &self.price_effect))?;
+        describe_terms(
+            f,
+            &self.order_type,
+            Some(self.price),
+            &self.price_effect,
+            None,
+            &self.time_in_force,
+        )
+    }
+}
+
+impl fmt::Display for LiveOrderRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.legs.is_empty() {
+            write!(f, "{} x{}", self.underlying_symbol.0, self.size)?;
+        } else {
+            let legs: Vec<LegView> = self
+                .legs
+                .iter()
+                .map(|leg| LegView {
+                    instrument_type: &leg.instrument_type,
+                    symbol: &leg.symbol,
+                    quantity: leg.quantity,
+                    action: &leg.action,
+                })
+                .collect();
+            write!(f, "{}", describe_legs(&legs, &self.price_effect))?;
+        }
+        describe_terms(
+            f,
+            &self.order_type,
+            Some(self.price),
+            &self.price_effect,
+            None,
+            &self.time_in_force,
+        )?;
+        write!(f, " ({:?})", self.status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::order::{OrderBuilder, OrderLegBuilder};
+    use serde_json::json;
+    use std::str::FromStr;
+
+    fn leg(symbol: &str, qty: i64, action: Action) -> OrderLeg {
+        let instrument_type = if symbol.len() > 15 {
+            InstrumentType::EquityOption
+        } else {
+            InstrumentType::Equity
+        };
+        OrderLegBuilder::default()
+            .instrument_type(instrument_type)
+            .symbol(symbol)
+            .quantity(Decimal::from(qty))
+            .action(action)
+            .build()
+            .unwrap()
+    }
+
+    fn limit(price: &str, effect: PriceEffect, legs: Vec<OrderLeg>) -> Order {
+        OrderBuilder::default()
+            .time_in_force(TimeInForce::Day)
+            .order_type(OrderType::Limit)
+            .price(Decimal::from_str(price).unwrap())
+            .price_effect(effect)
+            .legs(legs)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_single_legs() {
+        let order = limit(
+            "150.25",
+            PriceEffect::Debit,
+            vec![leg("AAPL", 100, Action::Buy)],
+        );
+        assert_eq!(order.to_string(), "Buy 100 AAPL @ 150.25 db, Day");
+
+        let order = limit(
+            "1.5",
+            PriceEffect::Credit,
+            vec![leg("AAPL  240119C00150000", 2, Action::SellToOpen)],
+        );
+        assert_eq!(
+            order.to_string(),
+            "Sell 2 AAPL 19Jan24 150 Call @ 1.50 cr, Day"
+        );
+
+        let market = OrderBuilder::default()
+            .time_in_force(TimeInForce::GTC)
+            .order_type(OrderType::Market)
+            .price_effect(PriceEffect::Debit)
+            .legs(vec![leg("SPY", 10, Action::Buy)])
+            .build()
+            .unwrap();
+        assert_eq!(market.to_string(), "Buy 10 SPY @ MKT, GTC");
+    }
+
+    #[test]
+    fn test_vertical_and_condor() {
+        let vertical = limit(
+            "1.25",
+            PriceEffect::Credit,
+            vec![
+                leg("AAPL  240119C00150000", 1, Action::SellToOpen),
+                leg("AAPL  240119C00155000", 1, Action::BuyToOpen),
+            ],
+        );
+        assert_eq!(
+            vertical.to_string(),
+            "Sell 1 AAPL 19Jan24 150/155 Call Vertical @ 1.25 cr, Day"
+        );
+
+        let condor = limit(
+            "2.10",
+            PriceEffect::Credit,
+            vec![
+                leg("SPY   240216P00430000", 3, Action::BuyToOpen),
+                leg("SPY   240216P00440000", 3, Action::SellToOpen),
+                leg("SPY   240216C00470000", 3, Action::SellToOpen),
+                leg("SPY   240216C00480000", 3, Action::BuyToOpen),
+            ],
+        );
+        assert_eq!(
+            condor.to_string(),
+            "Sell 3 SPY 16Feb24 430/440/470/480 Iron Condor @ 2.10 cr, Day"
+        );
+    }
+
+    #[test]
+    fn test_calendar_strangle_butterfly_and_custom() {
+        let calendar = limit(
+            "0.80",
+            PriceEffect::Debit,
+            vec![
+                leg("AAPL  240119P00150000", 1, Action::SellToOpen),
+                leg("AAPL  240216P00150000", 1, Action::BuyToOpen),
+            ],
+        );
+        assert_eq!(
+            calendar.to_string(),
+            "Buy 1 AAPL 19Jan24/16Feb24 150 Put Calendar @ 0.80 db, Day"
+        );
+
+        let strangle = limit(
+            "3.00",
+            PriceEffect::Credit,
+            vec![
+                leg("AAPL  240119P00140000", 1, Action::SellToOpen),
+                leg("AAPL  240119C00160000", 1, Action::SellToOpen),
+            ],
+        );
+        assert_eq!(
+            strangle.to_string(),
+            "Sell 1 AAPL 19Jan24 140/160 Strangle @ 3.00 cr, Day"
+        );
+
+        let fly = limit(
+            "0.45",
+            PriceEffect::Debit,
+            vec![
+                leg("AAPL  240119C00145000", 1, Action::BuyToOpen),
+                leg("AAPL  240119C00150000", 2, Action::SellToOpen),
+                leg("AAPL  240119C00155000", 1, Action::BuyToOpen),
+            ],
+        );
+        assert_eq!(
+            fly.to_string(),
+            "Buy 1 AAPL 19Jan24 145/150/155 Call Butterfly @ 0.45 db, Day"
+        );
+
+        let custom = limit(
+            "1.00",
+            PriceEffect::Debit,
+            vec![
+                leg("AAPL  240119C00145000", 1, Action::BuyToOpen),
+                leg("MSFT  240119C00400000", 1, Action::SellToOpen),
+            ],
+        );
+        assert_eq!(
+            custom.to_string(),
+            "Buy 1 AAPL 19Jan24 145 Call / Sell 1 MSFT 19Jan24 400 Call @ 1.00 db, Day"
+        );
+    }
+
+    #[test]
+    fn test_covered_call() {
+        let order = limit(
+            "148.50",
+            PriceEffect::Debit,
+            vec![
+                leg("AAPL", 100, Action::Buy),
+                leg("AAPL  240119C00150000", 1, Action::SellToOpen),
+            ],
+        );
+        assert_eq!(
+            order.to_string(),
+            "Buy 1 AAPL 19Jan24 150 Covered Call @ 148.50 db, Day"
+        );
+
+        let ten_lot = limit(
+            "148.50",
+            PriceEffect::Debit,
+            vec![
+                leg("AAPL", 1000, Action::Buy),
+                leg("AAPL  240119C00150000", 10, Action::SellToOpen),
+            ],
+        );
+        assert_eq!(
+            ten_lot.to_string(),
+            "Buy 10 AAPL 19Jan24 150 Covered Call @ 148.50 db, Day"
+        );
+
+        // 100 shares do not cover 5 calls
+        let uncovered = limit(
+            "148.50",
+            PriceEffect::Debit,
+            vec![
+                leg("AAPL", 100, Action::Buy),
+                leg("AAPL  240119C00150000", 5, Action::SellToOpen),
+            ],
+        );
+        assert!(!uncovered.to_string().contains("Covered"));
+    }
+
+    #[test]
+    fn test_live_order_record_display() {
+        let record: LiveOrderRecord = serde_json::from_value(json!({
+            "id": 1,
+            "account-number": "ACC123",
+            "time-in-force": "GTC",
+            "order-type": "Limit",
+            "size": 1,
+            "underlying-symbol": "AAPL",
+            "price": "1.25",
+            "price-effect": "Credit",
+            "status": "Live",
+            "cancellable": true,
+            "editable": true,
+            "edited": false,
+            "legs": [
+                {
+                    "instrument-type": "Equity Option",
+                    "symbol": "AAPL  240119P00150000",
+                    "quantity": 1,
+                    "remaining-quantity": 1,
+                    "action": "Sell to Open",
+                    "fills": []
+                },
+                {
+                    "instrument-type": "Equity Option",
+                    "symbol": "AAPL  240119P00145000",
+                    "quantity": 1,
+                    "remaining-quantity": 1,
+                    "action": "Buy to Open",
+                    "fills": []
+                }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            record.to_string(),
+            "Sell 1 AAPL 19Jan24 145/150 Put Vertical @ 1.25 cr, GTC (Live)"
+        );
+    }
+}
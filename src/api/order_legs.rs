@@ -0,0 +1,280 @@
+//! Checked construction of futures, futures option and cryptocurrency orders.
+//!
+//! The constructors here reject malformed symbols and quantities with the
+//! wrong precision before anything is sent, and
+//! [`TastyTrade::validate_order_symbols`] confirms every leg against the
+//! instruments API.
+//!
+//! # Example
+//! ```ignore
+//! let leg = OrderLeg::future("/ESZ4", Decimal::ONE, Action::BuyToOpen)?;
+//! let btc = Order::crypto_notional("BTC/USD", Decimal::from(250), Action::BuyToOpen)?;
+//! client.validate_order_symbols(&btc).await?;
+//! ```
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rust_decimal::Decimal;
+
+use crate::api::base::{Result, TastyError};
+use crate::client::TastyTrade;
+
+use super::future_symbol::{FutureOptionSymbol, FutureSymbol};
+use super::order::{
+    Action, AsSymbol, InstrumentType, Order, OrderLeg, OrderType, PriceEffect, Symbol, TimeInForce,
+};
+use super::order_validation::OrderValidationError;
+
+/// Decimal places accepted for cryptocurrency quantities
+pub const CRYPTO_QUANTITY_SCALE: u32 = 8;
+
+static CRYPTO_SYMBOL: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Z0-9]{2,10}/USD$").unwrap());
+
+/// Check the shape of a futures, futures option or crypto symbol.
+///
+/// Other instrument types are accepted as-is.
+pub fn validate_symbol(
+    instrument_type: &InstrumentType,
+    symbol: &Symbol,
+) -> std::result::Result<(), OrderValidationError> {
+    let valid = match instrument_type {
+        InstrumentType::Future => FutureSymbol::parse(&symbol.0).is_ok(),
+        InstrumentType::FutureOption => FutureOptionSymbol::parse(&symbol.0).is_ok(),
+        InstrumentType::Cryptocurrency => CRYPTO_SYMBOL.is_match(&symbol.0),
+        _ => return Ok(()),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(OrderValidationError::InvalidSymbol {
+            symbol: symbol.0.clone(),
+            instrument_type: instrument_type.as_api_str().to_string(),
+        })
+    }
+}
+
+/// Maximum decimal places of an order quantity, if the type restricts it.
+///
+/// Options and futures trade in whole contracts; crypto trades in fractions
+/// down to [`CRYPTO_QUANTITY_SCALE`] places.
+pub fn quantity_scale(instrument_type: &InstrumentType) -> Option<u32> {
+    match instrument_type {
+        InstrumentType::EquityOption | InstrumentType::Future | InstrumentType::FutureOption => {
+            Some(0)
+        }
+        InstrumentType::Cryptocurrency => Some(CRYPTO_QUANTITY_SCALE),
+        _ => None,
+    }
+}
+
+/// Check that `quantity` is positive and has an allowed precision, returning
+/// it normalized.
+pub fn validate_quantity(
+    instrument_type: &InstrumentType,
+    symbol: &Symbol,
+    quantity: Decimal,
+) -> std::result::Result<Decimal, OrderValidationError> {
+    let quantity = quantity.normalize();
+    let too_precise = quantity_scale(instrument_type).is_some_and(|scale| quantity.scale() > scale);
+    if quantity <= Decimal::ZERO || too_precise {
+        return Err(OrderValidationError::InvalidQuantity {
+            symbol: symbol.0.clone(),
+            quantity,
+        });
+    }
+    Ok(quantity)
+}
+
+impl OrderLeg {
+    /// A leg on a futures contract such as `/ESZ4`, in whole contracts.
+    pub fn future(
+        symbol: impl AsSymbol,
+        quantity: Decimal,
+        action: Action,
+    ) -> std::result::Result<Self, OrderValidationError> {
+        Self::checked(InstrumentType::Future, symbol.as_symbol(), quantity, action)
+    }
+
+    /// A leg on a futures option such as `./ESZ4 EW4U4 240920C5000`, in whole contracts.
+    pub fn future_option(
+        symbol: impl AsSymbol,
+        quantity: Decimal,
+        action: Action,
+    ) -> std::result::Result<Self, OrderValidationError> {
+        Self::checked(
+            InstrumentType::FutureOption,
+            symbol.as_symbol(),
+            quantity,
+            action,
+        )
+    }
+
+    /// A leg on a cryptocurrency pair such as `BTC/USD`, with up to eight decimal places.
+    pub fn crypto(
+        symbol: impl AsSymbol,
+        quantity: Decimal,
+        action: Action,
+    ) -> std::result::Result<Self, OrderValidationError> {
+        Self::checked(
+            InstrumentType::Cryptocurrency,
+            symbol.as_symbol(),
+            quantity,
+            action,
+        )
+    }
+
+    fn checked(
+        instrument_type: InstrumentType,
+        symbol: Symbol,
+        quantity: Decimal,
+        action: Action,
+    ) -> std::result::Result<Self, OrderValidationError> {
+        validate_symbol(&instrument_type, &symbol)?;
+        let quantity = validate_quantity(&instrument_type, &symbol, quantity)?;
+        Ok(Self {
+            instrument_type,
+            symbol,
+            quantity,
+            action,
+        })
+    }
+}
+
+impl Order {
+    /// A `Notional Market` crypto order for a dollar amount rather than a quantity.
+    ///
+    /// The order is sent IOC; the leg omits its quantity and `value` sizes the trade.
+    pub fn crypto_notional(
+        symbol: impl AsSymbol,
+        value: Decimal,
+        action: Action,
+    ) -> std::result::Result<Self, OrderValidationError> {
+        let symbol = symbol.as_symbol();
+        validate_symbol(&InstrumentType::Cryptocurrency, &symbol)?;
+        let value = value.normalize();
+        if value <= Decimal::ZERO || value.scale() > 2 {
+            return Err(OrderValidationError::InvalidNotional(value));
+        }
+        let effect = if action.is_buy() {
+            PriceEffect::Debit
+        } else {
+            PriceEffect::Credit
+        };
+        Ok(Self {
+            time_in_force: TimeInForce::IOC,
+            order_type: OrderType::NotionalMarket,
+            price: None,
+            price_effect: effect.clone(),
+            stop_trigger: None,
+            value: Some(value),
+            value_effect: Some(effect),
+            legs: vec![OrderLeg {
+                instrument_type: InstrumentType::Cryptocurrency,
+                symbol,
+                quantity: Decimal::ZERO,
+                action,
+            }],
+        })
+    }
+}
+
+impl TastyTrade {
+    /// Check every leg's symbol and quantity locally, then confirm each
+    /// instrument exists, is active and accepts the leg's action.
+    ///
+    /// Legs of types without an instruments endpoint are only checked locally.
+    /// A symbol the API does not know fails with
+    /// [`OrderValidationError::UnknownSymbol`]; any other API error is returned as is.
+    pub async fn validate_order_symbols(&self, order: &Order) -> Result<()> {
+        for leg in &order.legs {
+            validate_symbol(&leg.instrument_type, &leg.symbol)?;
+            let notional_leg = order.value.is_some() && leg.quantity.is_zero();
+            if !notional_leg {
+                validate_quantity(&leg.instrument_type, &leg.symbol, leg.quantity)?;
+            }
+
+            let summary = match self
+                .get_instrument_summary(&leg.instrument_type, &leg.symbol)
+                .await
+            {
+                Ok(summary) => summary,
+                Err(e) if e.is_not_found() => {
+                    return Err(OrderValidationError::UnknownSymbol(leg.symbol.0.clone()).into())
+                }
+                Err(TastyError::Config(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            if summary.active == Some(false) {
+                return Err(OrderValidationError::Inactive(leg.symbol.0.clone()).into());
+            }
+            if summary.is_closing_only == Some(true) && !leg.action.is_closing() {
+                return Err(OrderValidationError::ClosingOnly(leg.symbol.0.clone()).into());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::dec;
+
+    #[test]
+    fn test_symbol_shapes() {
+        let ok = |t: InstrumentType, s: &str| validate_symbol(&t, &Symbol::from(s)).is_ok();
+        assert!(ok(InstrumentType::Future, "/ESZ4"));
+        assert!(ok(InstrumentType::Future, "/CLF25"));
+        assert!(!ok(InstrumentType::Future, "ESZ4"));
+        assert!(!ok(InstrumentType::Future, "/ESA4"));
+        assert!(ok(InstrumentType::FutureOption, "./ESZ4 EW4U4 240920C5000"));
+        assert!(ok(InstrumentType::FutureOption, "./CLZ4 LO1X4 241014P70.5"));
+        assert!(!ok(InstrumentType::FutureOption, "/ESZ4"));
+        assert!(ok(InstrumentType::Cryptocurrency, "BTC/USD"));
+        assert!(!ok(InstrumentType::Cryptocurrency, "BTCUSD"));
+        assert!(ok(InstrumentType::Equity, "anything"));
+    }
+
+    #[test]
+    fn test_quantity_precision() {
+        assert!(OrderLeg::future("/ESZ4", dec("2"), Action::BuyToOpen).is_ok());
+        assert!(matches!(
+            OrderLeg::future("/ESZ4", dec("1.5"), Action::BuyToOpen),
+            Err(OrderValidationError::InvalidQuantity { .. })
+        ));
+        assert!(
+            OrderLeg::future_option("./ESZ4 EW4U4 240920C5000", dec("0"), Action::Buy).is_err()
+        );
+
+        // Trailing zeros do not count against the precision limit
+        let leg = OrderLeg::crypto("BTC/USD", dec("0.12345678000"), Action::BuyToOpen).unwrap();
+        assert_eq!(leg.quantity(), dec("0.12345678"));
+        assert!(OrderLeg::crypto("BTC/USD", dec("0.123456789"), Action::BuyToOpen).is_err());
+    }
+
+    #[test]
+    fn test_crypto_quantity_serializes_exactly() {
+        let leg = OrderLeg::crypto("ETH/USD", dec("0.00012345"), Action::BuyToOpen).unwrap();
+        let json = serde_json::to_string(&leg).unwrap();
+        assert!(json.contains(r#""quantity":0.00012345"#), "{json}");
+    }
+
+    #[test]
+    fn test_crypto_notional_order() {
+        let order = Order::crypto_notional("BTC/USD", dec("250"), Action::BuyToOpen).unwrap();
+        assert_eq!(order.order_type(), &OrderType::NotionalMarket);
+        assert_eq!(order.value(), Some(dec("250")));
+
+        let json = serde_json::to_value(&order).unwrap();
+        assert_eq!(json["order-type"], "Notional Market");
+        assert_eq!(json["value-effect"], "Debit");
+        assert!(json["legs"][0].get("quantity").is_none());
+        assert_eq!(order.to_string(), "Buy BTC/USD $250.00 notional @ MKT, IOC");
+
+        assert!(matches!(
+            Order::crypto_notional("BTC/USD", dec("10.001"), Action::BuyToOpen),
+            Err(OrderValidationError::InvalidNotional(_))
+        ));
+        assert!(Order::crypto_notional("BTC", dec("10"), Action::BuyToOpen).is_err());
+    }
+}
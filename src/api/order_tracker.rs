@@ -0,0 +1,385 @@
+//! Order lifecycle tracking on top of the account streamer.
+//!
+//! [`OrderTracker`] keeps the latest [`LiveOrderRecord`] for every order it has
+//! seen, publishes status transitions, and lets callers wait for an order to
+//! reach a terminal state. Drive it with [`OrderTracker::run`], which consumes
+//! [`AccountStreamer`] events and falls back to polling the REST API while the
+//! stream is down, or with [`OrderTracker::run_with_events`] when the
+//! streamer's events are also needed elsewhere.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Mutex, Notify};
+use tracing::{debug, warn};
+
+use crate::api::account_streaming::{AccountEvent, AccountMessage, AccountStreamer, StreamEvent};
+use crate::api::accounts::Account;
+
+use super::order::{LiveOrderRecord, OrderId, OrderStatus};
+
+/// Default interval between REST polls while the account stream is down
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum OrderTrackerError {
+    #[error("timed out waiting for order {0:?}")]
+    Timeout(OrderId),
+    #[error("order {id:?} finished as {status:?} without filling")]
+    NotFilled { id: OrderId, status: OrderStatus },
+}
+
+/// A change in an order's status.
+#[derive(Debug, Clone)]
+pub struct OrderTransition {
+    pub order_id: OrderId,
+    /// Previous status, or `None` the first time the order is seen
+    pub from: Option<OrderStatus>,
+    pub to: OrderStatus,
+    pub record: LiveOrderRecord,
+}
+
+#[derive(Debug, Default)]
+struct TrackerInner {
+    orders: Mutex<HashMap<OrderId, LiveOrderRecord>>,
+    subscribers: Mutex<Vec<flume::Sender<OrderTransition>>>,
+    updated: Notify,
+}
+
+/// Per-order state machine fed by account streamer events.
+///
+/// Cloning is cheap; clones share the same state.
+///
+/// # Example
+/// ```ignore
+/// let tracker = OrderTracker::new();
+/// let streamer = tasty.create_account_streamer().await?;
+/// streamer.subscribe_to_account(&account).await;
+///
+/// let placed = account.place_order(&order).await?;
+/// tokio::select! {
+///     _ = tracker.run(&streamer, &account, DEFAULT_POLL_INTERVAL) => {}
+///     fill = tracker.await_fill(placed.order.id, Duration::from_secs(30)) => {
+///         println!("filled: {:?}", fill?);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OrderTracker {
+    inner: Arc<TrackerInner>,
+}
+
+impl OrderTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an order update, returning the transition if its status changed.
+    ///
+    /// Updates that would move an order out of a terminal state are treated as
+    /// stale and ignored.
+    pub async fn apply(&self, record: LiveOrderRecord) -> Option<OrderTransition> {
+        let transition = {
+            let mut orders = self.inner.orders.lock().await;
+            let from = orders.get(&record.id).map(|existing| existing.status);
+            if from.is_some_and(|from| from.is_terminal() && !record.status.is_terminal()) {
+                debug!(
+                    "Ignoring stale update for order {:?}: {:?}",
+                    record.id, record.status
+                );
+                return None;
+            }
+            orders.insert(record.id, record.clone());
+            if from == Some(record.status) {
+                None
+            } else {
+                Some(OrderTransition {
+                    order_id: record.id,
+                    from,
+                    to: record.status,
+                    record,
+                })
+            }
+        };
+
+        if let Some(transition) = &transition {
+            self.inner
+                .subscribers
+                .lock()
+                .await
+                .retain(|tx| tx.send(transition.clone()).is_ok());
+        }
+        self.inner.updated.notify_waiters();
+        transition
+    }
+
+    /// Apply an account streamer event if it carries an order update.
+    pub async fn handle_event(&self, event: &StreamEvent) -> Option<OrderTransition> {
+        match event {
+            StreamEvent::Account(AccountEvent::AccountMessage(message)) => match message.as_ref() {
+                AccountMessage::Order(record) => self.apply(record.clone()).await,
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Subscribe to status transitions applied from now on.
+    pub async fn transitions(&self) -> flume::Receiver<OrderTransition> {
+        let (tx, rx) = flume::unbounded();
+        self.inner.subscribers.lock().await.push(tx);
+        rx
+    }
+
+    /// The latest known record for an order.
+    pub async fn get(&self, id: &OrderId) -> Option<LiveOrderRecord> {
+        self.inner.orders.lock().await.get(id).cloned()
+    }
+
+    /// The latest known status for an order.
+    pub async fn status(&self, id: &OrderId) -> Option<OrderStatus> {
+        self.inner.orders.lock().await.get(id).map(|o| o.status)
+    }
+
+    /// Ids of tracked orders that are still working.
+    pub async fn working_orders(&self) -> Vec<OrderId> {
+        self.inner
+            .orders
+            .lock()
+            .await
+            .values()
+            .filter(|o| o.status.is_working())
+            .map(|o| o.id)
+            .collect()
+    }
+
+    /// Wait until the order reaches a terminal status.
+    pub async fn await_terminal(&self, id: OrderId) -> LiveOrderRecord {
+        loop {
+            let notified = self.inner.updated.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if let Some(record) = self.get(&id).await {
+                if record.status.is_terminal() {
+                    return record;
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// Wait up to `timeout` for the order to fill.
+    ///
+    /// Fails if the order ends in any other terminal status or the timeout elapses.
+    pub async fn await_fill(
+        &self,
+        id: OrderId,
+        timeout: Duration,
+    ) -> std::result::Result<LiveOrderRecord, OrderTrackerError> {
+        let record = tokio::time::timeout(timeout, self.await_terminal(id))
+            .await
+            .map_err(|_| OrderTrackerError::Timeout(id))?;
+        match record.status {
+            OrderStatus::Filled => Ok(record),
+            status => Err(OrderTrackerError::NotFilled { id, status }),
+        }
+    }
+
+    /// Refresh tracked working orders from the REST API.
+    ///
+    /// Fails only if the live order list cannot be fetched; an order that
+    /// cannot be looked up on its own is logged and skipped.
+    pub async fn poll(&self, account: &Account<'_>) -> crate::Result<()> {
+        for record in account.live_orders().await? {
+            self.apply(record).await;
+        }
+        // Orders that dropped off the live list still need their final status
+        for id in self.working_orders().await {
+            match account.order(id).await {
+                Ok(record) => {
+                    self.apply(record).await;
+                }
+                Err(e) => warn!("Failed to refresh order {:?}: {}", id, e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Consume order events from `streamer`, polling `account` every
+    /// `poll_interval` while the stream is disconnected.
+    ///
+    /// This takes every event off the streamer's channel, so nothing else can
+    /// read from the same streamer while it runs. To share a streamer, read its
+    /// events yourself and pass each one to [`handle_event`](Self::handle_event),
+    /// or forward them into a channel for [`run_with_events`](Self::run_with_events).
+    ///
+    /// Runs until the returned future is dropped.
+    pub async fn run(
+        &self,
+        streamer: &AccountStreamer,
+        account: &Account<'_>,
+        poll_interval: Duration,
+    ) {
+        self.run_with_events(streamer.event_receiver.clone(), account, poll_interval)
+            .await
+    }
+
+    /// Like [`run`](Self::run), but reads events from a dedicated channel.
+    pub async fn run_with_events(
+        &self,
+        events: flume::Receiver<StreamEvent>,
+        account: &Account<'_>,
+        poll_interval: Duration,
+    ) {
+        let mut streaming = true;
+        let mut stream_open = true;
+        let mut interval = tokio::time::interval(poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                event = events.recv_async(), if stream_open => match event {
+                    Ok(event) => {
+                        match &event {
+                            StreamEvent::Disconnected { reason } | StreamEvent::Closed { reason } => {
+                                warn!("Order stream down ({}), polling for order updates", reason);
+                                streaming = false;
+                            }
+                            StreamEvent::Reconnected => {
+                                streaming = true;
+                                // Catch up on anything missed while disconnected
+                                if let Err(e) = self.poll(account).await {
+                                    warn!("Failed to poll orders after reconnect: {}", e);
+                                }
+                            }
+                            _ => {}
+                        }
+                        self.handle_event(&event).await;
+                    }
+                    Err(_) => {
+                        warn!("Order stream closed, polling for order updates");
+                        streaming = false;
+                        stream_open = false;
+                    }
+                },
+                _ = interval.tick(), if !streaming => {
+                    if let Err(e) = self.poll(account).await {
+                        warn!("Failed to poll orders: {}", e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(id: u64, status: &str) -> LiveOrderRecord {
+        serde_json::from_value(json!({
+            "id": id,
+            "account-number": "ACC123",
+            "time-in-force": "Day",
+            "order-type": "Limit",
+            "size": 1,
+            "underlying-symbol": "AAPL",
+            "price": "150.25",
+            "price-effect": "Debit",
+            "status": status,
+            "cancellable": true,
+            "editable": true,
+            "edited": false
+        }))
+        .unwrap()
+    }
+
+    fn order_event(id: u64, status: &str) -> StreamEvent {
+        StreamEvent::Account(AccountEvent::AccountMessage(Box::new(
+            AccountMessage::Order(record(id, status)),
+        )))
+    }
+
+    #[tokio::test]
+    async fn test_transitions_are_published_once_per_status_change() {
+        let tracker = OrderTracker::new();
+        let rx = tracker.transitions().await;
+
+        assert!(tracker
+            .handle_event(&order_event(1, "Received"))
+            .await
+            .is_some());
+        assert!(tracker
+            .handle_event(&order_event(1, "Received"))
+            .await
+            .is_none());
+        assert!(tracker
+            .handle_event(&order_event(1, "Live"))
+            .await
+            .is_some());
+        assert!(tracker
+            .handle_event(&StreamEvent::Reconnected)
+            .await
+            .is_none());
+
+        let first = rx.try_recv().unwrap();
+        assert_eq!(first.from, None);
+        assert_eq!(first.to, OrderStatus::Received);
+        let second = rx.try_recv().unwrap();
+        assert_eq!(second.from, Some(OrderStatus::Received));
+        assert_eq!(second.to, OrderStatus::Live);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(tracker.status(&OrderId(1)).await, Some(OrderStatus::Live));
+    }
+
+    #[tokio::test]
+    async fn test_stale_update_after_terminal_is_ignored() {
+        let tracker = OrderTracker::new();
+        tracker.apply(record(1, "Filled")).await;
+        assert!(tracker.apply(record(1, "Live")).await.is_none());
+        assert_eq!(tracker.status(&OrderId(1)).await, Some(OrderStatus::Filled));
+        assert!(tracker.working_orders().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_await_fill() {
+        let tracker = OrderTracker::new();
+        tracker.apply(record(7, "Live")).await;
+
+        let waiter = {
+            let tracker = tracker.clone();
+            tokio::spawn(
+                async move { tracker.await_fill(OrderId(7), Duration::from_secs(5)).await },
+            )
+        };
+        tokio::task::yield_now().await;
+        tracker.apply(record(7, "Filled")).await;
+
+        let filled = waiter.await.unwrap().unwrap();
+        assert_eq!(filled.status, OrderStatus::Filled);
+    }
+
+    #[tokio::test]
+    async fn test_await_fill_rejected_and_timeout() {
+        let tracker = OrderTracker::new();
+        tracker.apply(record(8, "Rejected")).await;
+        assert!(matches!(
+            tracker.await_fill(OrderId(8), Duration::from_secs(1)).await,
+            Err(OrderTrackerError::NotFilled {
+                status: OrderStatus::Rejected,
+                ..
+            })
+        ));
+
+        tracker.apply(record(9, "Live")).await;
+        assert!(matches!(
+            tracker
+                .await_fill(OrderId(9), Duration::from_millis(20))
+                .await,
+            Err(OrderTrackerError::Timeout(OrderId(9)))
+        ));
+    }
+}
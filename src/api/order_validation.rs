@@ -0,0 +1,459 @@
+//! Client-side order validation against instrument metadata.
+//!
+//! Tick size schedules returned by the instruments endpoints are parsed into
+//! [`TickSchedule`]s, which an [`OrderValidator`] uses to snap or reject
+//! off-tick limit and stop prices before an order ever reaches the API.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use super::instrument::{EquityInstrumentInfo, TickSize};
+use super::order::{Order, OrderBuilder};
+
+#[derive(Debug, thiserror::Error)]
+pub enum OrderValidationError {
+    #[error("price {price} is not a multiple of tick size {tick}")]
+    OffTick { price: Decimal, tick: Decimal },
+    #[error("invalid tick size entry: {0}")]
+    InvalidTickSize(String),
+    #[error("{0} is closing-only and the order contains opening legs")]
+    ClosingOnly(String),
+    #[error("{0} is not active")]
+    Inactive(String),
+    #[error("order could not be built: {0}")]
+    Builder(String),
+    #[error("{symbol} is not a valid {instrument_type} symbol")]
+    InvalidSymbol {
+        symbol: String,
+        instrument_type: String,
+    },
+    #[error("quantity {quantity} is not valid for {symbol}")]
+    InvalidQuantity { symbol: String, quantity: Decimal },
+    #[error("notional value {0} must be positive and in whole cents")]
+    InvalidNotional(Decimal),
+    #[error("{0} was not found by the instruments API")]
+    UnknownSymbol(String),
+}
+
+/// A single tier of a tick size schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickTier {
+    /// Prices strictly below this threshold use `tick`; `None` means no upper bound
+    pub threshold: Option<Decimal>,
+    /// Minimum price increment for this tier
+    pub tick: Decimal,
+}
+
+/// Parsed tick size schedule, ordered by ascending threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TickSchedule {
+    tiers: Vec<TickTier>,
+}
+
+impl TickSchedule {
+    /// Build a schedule from tiers, sorting them by threshold.
+    pub fn new(mut tiers: Vec<TickTier>) -> Self {
+        tiers.sort_by(|a, b| match (a.threshold, b.threshold) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        Self { tiers }
+    }
+
+    /// A flat one-cent schedule, used for equities without tick metadata.
+    pub fn penny() -> Self {
+        Self::new(vec![TickTier {
+            threshold: None,
+            tick: Decimal::new(1, 2),
+        }])
+    }
+
+    /// The standard option schedule: $0.01 below $3.00 and $0.05 at or above.
+    pub fn standard_option() -> Self {
+        Self::new(vec![
+            TickTier {
+                threshold: Some(Decimal::new(3, 0)),
+                tick: Decimal::new(1, 2),
+            },
+            TickTier {
+                threshold: None,
+                tick: Decimal::new(5, 2),
+            },
+        ])
+    }
+
+    /// Parse the raw `tick-sizes` entries from the instruments API.
+    pub fn from_tick_sizes(
+        tick_sizes: &[TickSize],
+    ) -> std::result::Result<Self, OrderValidationError> {
+        let tiers = tick_sizes
+            .iter()
+            .map(|ts| {
+                let tick = parse_decimal(&ts.value)?;
+                if tick <= Decimal::ZERO {
+                    return Err(OrderValidationError::InvalidTickSize(ts.value.clone()));
+                }
+                let threshold = ts.threshold.as_deref().map(parse_decimal).transpose()?;
+                Ok(TickTier { threshold, tick })
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        if tiers.is_empty() {
+            return Err(OrderValidationError::InvalidTickSize(
+                "empty tick size schedule".to_string(),
+            ));
+        }
+
+        Ok(Self::new(tiers))
+    }
+
+    pub fn tiers(&self) -> &[TickTier] {
+        &self.tiers
+    }
+
+    /// Returns the tick size that applies at `price`.
+    pub fn tick_for(&self, price: Decimal) -> Decimal {
+        let magnitude = price.abs();
+        self.tiers
+            .iter()
+            .find(|tier| tier.threshold.is_none_or(|t| magnitude < t))
+            .or(self.tiers.last())
+            .map(|tier| tier.tick)
+            .unwrap_or(Decimal::new(1, 2))
+    }
+
+    /// Whether `price` is a whole multiple of the tick that applies to it.
+    pub fn is_on_tick(&self, price: Decimal) -> bool {
+        (price % self.tick_for(price)).is_zero()
+    }
+
+    /// Round `price` to the tick that applies to it using `strategy`.
+    pub fn round(&self, price: Decimal, strategy: RoundingStrategy) -> Decimal {
+        let tick = self.tick_for(price);
+        let rounded = (price / tick).round_dp_with_strategy(0, strategy) * tick;
+        // Rounding across a threshold can land on a coarser tier; snap again there.
+        if self.is_on_tick(rounded) {
+            rounded.normalize()
+        } else {
+            let tick = self.tick_for(rounded);
+            ((rounded / tick).round_dp_with_strategy(0, strategy) * tick).normalize()
+        }
+    }
+}
+
+fn parse_decimal(value: &str) -> std::result::Result<Decimal, OrderValidationError> {
+    value
+        .trim()
+        .parse::<Decimal>()
+        .map_err(|_| OrderValidationError::InvalidTickSize(value.to_string()))
+}
+
+/// What to do with a price that is not on a valid tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickPolicy {
+    /// Fail validation with [`OrderValidationError::OffTick`]
+    Reject,
+    /// Round the price onto the tick grid with the given strategy
+    Snap(RoundingStrategy),
+}
+
+/// Validates orders for a single instrument before submission.
+///
+/// # Example
+/// ```ignore
+/// let info = client.get_equity_info("AAPL").await?;
+/// let validator = OrderValidator::for_equity_option(&info)?
+///     .with_policy(TickPolicy::Snap(RoundingStrategy::MidpointAwayFromZero));
+/// let order = OrderBuilder::default()
+///     // ...
+///     .build_validated(&validator)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderValidator {
+    symbol: String,
+    schedule: TickSchedule,
+    policy: TickPolicy,
+    closing_only: bool,
+    active: bool,
+}
+
+impl OrderValidator {
+    /// Create a validator from an explicit schedule with no tradability restrictions.
+    pub fn new(symbol: impl Into<String>, schedule: TickSchedule) -> Self {
+        Self {
+            symbol: symbol.into(),
+            schedule,
+            policy: TickPolicy::Reject,
+            closing_only: false,
+            active: true,
+        }
+    }
+
+    /// Validator for orders on the equity itself, using `tick-sizes`.
+    pub fn for_equity(
+        info: &EquityInstrumentInfo,
+    ) -> std::result::Result<Self, OrderValidationError> {
+        let schedule = match &info.tick_sizes {
+            Some(ticks) if !ticks.is_empty() => TickSchedule::from_tick_sizes(ticks)?,
+            _ => TickSchedule::penny(),
+        };
+        Ok(Self::new(info.symbol.0.clone(), schedule)
+            .with_closing_only(info.is_closing_only.unwrap_or(false))
+            .with_active(info.active.unwrap_or(true)))
+    }
+
+    /// Validator for option orders on this underlying, using `option-tick-sizes`.
+    pub fn for_equity_option(
+        info: &EquityInstrumentInfo,
+    ) -> std::result::Result<Self, OrderValidationError> {
+        let schedule = match &info.option_tick_sizes {
+            Some(ticks) if !ticks.is_empty() => TickSchedule::from_tick_sizes(ticks)?,
+            _ => TickSchedule::standard_option(),
+        };
+        // Indices such as SPX report `active: false` for the index itself while
+        // their options still trade.
+        let active = info.active.unwrap_or(true) || info.is_index.unwrap_or(false);
+        Ok(Self::new(info.symbol.0.clone(), schedule)
+            .with_closing_only(info.is_options_closing_only.unwrap_or(false))
+            .with_active(active))
+    }
+
+    pub fn with_policy(mut self, policy: TickPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_closing_only(mut self, closing_only: bool) -> Self {
+        self.closing_only = closing_only;
+        self
+    }
+
+    pub fn with_active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
+    pub fn schedule(&self) -> &TickSchedule {
+        &self.schedule
+    }
+
+    /// Apply the tick policy to a single price.
+    pub fn check_price(&self, price: Decimal) -> std::result::Result<Decimal, OrderValidationError> {
+        if self.schedule.is_on_tick(price) {
+            return Ok(price);
+        }
+        match self.policy {
+            TickPolicy::Reject => Err(OrderValidationError::OffTick {
+                price,
+                tick: self.schedule.tick_for(price),
+            }),
+            TickPolicy::Snap(strategy) => Ok(self.schedule.round(price, strategy)),
+        }
+    }
+
+    /// Check tradability and the limit/stop prices of `order`.
+    ///
+    /// Closing-only instruments accept an order only if every leg is a
+    /// to-close action; plain `Buy`/`Sell` legs are treated as opening.
+    pub fn validate(&self, mut order: Order) -> std::result::Result<Order, OrderValidationError> {
+        if !self.active {
+            return Err(OrderValidationError::Inactive(self.symbol.clone()));
+        }
+        if self.closing_only && !order.legs.iter().all(|leg| leg.action.is_closing()) {
+            return Err(OrderValidationError::ClosingOnly(self.symbol.clone()));
+        }
+
+        order.price = order.price.map(|p| self.check_price(p)).transpose()?;
+        order.stop_trigger = order
+            .stop_trigger
+            .map(|p| self.check_price(p))
+            .transpose()?;
+
+        Ok(order)
+    }
+}
+
+impl OrderBuilder {
+    /// Build the order and run it through `validator`.
+    pub fn build_validated(
+        &self,
+        validator: &OrderValidator,
+    ) -> std::result::Result<Order, OrderValidationError> {
+        let order = self
+            .build()
+            .map_err(|e| OrderValidationError::Builder(e.to_string()))?;
+        validator.validate(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::order::{
+        Action, InstrumentType, OrderLegBuilder, OrderType, PriceEffect, Symbol, TimeInForce,
+    };
+    use crate::api::test_util::dec;
+    use serde_json::json;
+
+    fn option_info() -> EquityInstrumentInfo {
+        serde_json::from_value(json!({
+            "symbol": "AAPL",
+            "streamer-symbol": "AAPL",
+            "active": true,
+            "is-closing-only": false,
+            "is-options-closing-only": false,
+            "tick-sizes": [
+                { "value": "0.0001", "threshold": "1.0" },
+                { "value": "0.01" }
+            ],
+            "option-tick-sizes": [
+                { "value": "0.05" },
+                { "value": "0.01", "threshold": "3.0" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn limit_order(price: &str, action: Action) -> OrderBuilder {
+        let leg = OrderLegBuilder::default()
+            .instrument_type(InstrumentType::EquityOption)
+            .symbol(Symbol::from("AAPL  240119C00150000"))
+            .quantity(Decimal::ONE)
+            .action(action)
+            .build()
+            .unwrap();
+        let mut builder = OrderBuilder::default();
+        builder
+            .time_in_force(TimeInForce::Day)
+            .order_type(OrderType::Limit)
+            .price(dec(price))
+            .price_effect(PriceEffect::Debit)
+            .legs(vec![leg]);
+        builder
+    }
+
+    #[test]
+    fn test_schedule_parses_and_sorts_tiers() {
+        let info = option_info();
+        let schedule =
+            TickSchedule::from_tick_sizes(info.option_tick_sizes.as_ref().unwrap()).unwrap();
+        assert_eq!(schedule.tiers()[0].threshold, Some(dec("3.0")));
+        assert_eq!(schedule.tiers()[1].threshold, None);
+        assert_eq!(schedule.tick_for(dec("2.99")), dec("0.01"));
+        assert_eq!(schedule.tick_for(dec("3.00")), dec("0.05"));
+        assert_eq!(schedule.tick_for(dec("12.40")), dec("0.05"));
+    }
+
+    #[test]
+    fn test_schedule_rejects_invalid_entries() {
+        let bad = vec![TickSize {
+            value: "abc".to_string(),
+            threshold: None,
+        }];
+        assert!(matches!(
+            TickSchedule::from_tick_sizes(&bad),
+            Err(OrderValidationError::InvalidTickSize(_))
+        ));
+        assert!(TickSchedule::from_tick_sizes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_is_on_tick_under_and_over_three_dollars() {
+        let schedule = TickSchedule::standard_option();
+        assert!(schedule.is_on_tick(dec("2.97")));
+        assert!(schedule.is_on_tick(dec("3.05")));
+        assert!(!schedule.is_on_tick(dec("3.07")));
+        assert!(!schedule.is_on_tick(dec("1.255")));
+    }
+
+    #[test]
+    fn test_round_snaps_to_applicable_tick() {
+        let schedule = TickSchedule::standard_option();
+        let nearest = RoundingStrategy::MidpointAwayFromZero;
+        assert_eq!(schedule.round(dec("3.07"), nearest), dec("3.05"));
+        assert_eq!(schedule.round(dec("3.08"), nearest), dec("3.10"));
+        assert_eq!(schedule.round(dec("1.234"), nearest), dec("1.23"));
+        assert_eq!(
+            schedule.round(dec("3.07"), RoundingStrategy::ToPositiveInfinity),
+            dec("3.10")
+        );
+        // 2.999 rounds up to 3.00, which is also valid on the nickel tier
+        assert_eq!(schedule.round(dec("2.999"), nearest), dec("3"));
+    }
+
+    #[test]
+    fn test_build_validated_rejects_off_tick_price() {
+        let validator = OrderValidator::for_equity_option(&option_info()).unwrap();
+        match limit_order("3.07", Action::BuyToOpen).build_validated(&validator) {
+            Err(OrderValidationError::OffTick { price, tick }) => {
+                assert_eq!(price, dec("3.07"));
+                assert_eq!(tick, dec("0.05"));
+            }
+            _ => panic!("Expected OffTick error"),
+        }
+
+        assert!(limit_order("2.97", Action::BuyToOpen)
+            .build_validated(&validator)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_build_validated_snaps_price_and_stop_trigger() {
+        let validator = OrderValidator::for_equity_option(&option_info())
+            .unwrap()
+            .with_policy(TickPolicy::Snap(RoundingStrategy::MidpointAwayFromZero));
+        let order = limit_order("3.08", Action::BuyToOpen)
+            .order_type(OrderType::StopLimit)
+            .stop_trigger(dec("3.52"))
+            .build_validated(&validator)
+            .unwrap();
+
+        let parsed = serde_json::to_value(&order).unwrap();
+        assert_eq!(order.price, Some(dec("3.10")));
+        assert_eq!(order.stop_trigger, Some(dec("3.50")));
+        assert!(parsed.get("stop-trigger").is_some());
+    }
+
+    #[test]
+    fn test_equity_validator_uses_sub_dollar_ticks() {
+        let validator = OrderValidator::for_equity(&option_info()).unwrap();
+        assert_eq!(validator.check_price(dec("0.5123")).unwrap(), dec("0.5123"));
+        assert!(validator.check_price(dec("12.345")).is_err());
+    }
+
+    #[test]
+    fn test_closing_only_and_inactive_instruments() {
+        let mut info = option_info();
+        info.is_options_closing_only = Some(true);
+        let validator = OrderValidator::for_equity_option(&info).unwrap();
+
+        assert!(matches!(
+            limit_order("1.00", Action::BuyToOpen).build_validated(&validator),
+            Err(OrderValidationError::ClosingOnly(_))
+        ));
+        assert!(limit_order("1.00", Action::BuyToClose)
+            .build_validated(&validator)
+            .is_ok());
+
+        info.active = Some(false);
+        let validator = OrderValidator::for_equity(&info).unwrap();
+        assert!(matches!(
+            limit_order("1.00", Action::SellToClose).build_validated(&validator),
+            Err(OrderValidationError::Inactive(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_tick_metadata_falls_back_to_defaults() {
+        let info: EquityInstrumentInfo = serde_json::from_value(json!({
+            "symbol": "XYZ",
+            "streamer-symbol": "XYZ"
+        }))
+        .unwrap();
+        let equity = OrderValidator::for_equity(&info).unwrap();
+        let option = OrderValidator::for_equity_option(&info).unwrap();
+        assert_eq!(equity.schedule(), &TickSchedule::penny());
+        assert_eq!(option.schedule(), &TickSchedule::standard_option());
+    }
+}
@@ -0,0 +1,1268 @@
+//! Local paper trading.
+//!
+//! [`PaperAccount`] implements [`TradingAccount`] entirely in memory, filling
+//! orders against quotes pushed in from a [`DxLinkQuoteStreamer`], a replayed
+//! feed, or by hand with [`PaperAccount::set_quote`]. It is a cash account:
+//! there is no margin, fills that would take cash below zero are rejected, and
+//! so are sells of more than the long position unless
+//! [`PaperConfig::allow_short`] is set.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::Utc;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use crate::api::accounts::{AccountNumber, Balance, TradingAccount};
+use crate::api::base::Result;
+use crate::api::position::{FullPosition, QuantityDirection};
+use crate::api::quote_streaming::{
+    DxLinkQuoteStreamer, QuoteData, StreamerEvent, StreamerEventData,
+};
+
+use super::order::{
+    Action, AsSymbol, BuyingPowerEffect, DryRunRecord, DryRunResult, FeeCalculation,
+    InstrumentType, LiveOrderLeg, LiveOrderRecord, Order, OrderId, OrderLeg, OrderPlacedResult,
+    OrderStatus, OrderType, PriceEffect, Symbol, TimeInForce,
+};
+
+/// Decimal places kept when sizing a notional order from its dollar value
+const NOTIONAL_QUANTITY_DP: u32 = 8;
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum PaperTradingError {
+    #[error("order has no legs")]
+    EmptyOrder,
+    #[error("limit price required for {0:?} orders")]
+    MissingPrice(OrderType),
+    #[error("stop trigger required for {0:?} orders")]
+    MissingStopTrigger(OrderType),
+    #[error("unknown order {0:?}")]
+    UnknownOrder(OrderId),
+    #[error("order {id:?} is {status:?} and cannot be cancelled")]
+    NotCancellable { id: OrderId, status: OrderStatus },
+}
+
+/// Price concession applied to every simulated fill, always against the trader.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Slippage {
+    #[default]
+    None,
+    /// Fixed amount per share/contract unit
+    Fixed(Decimal),
+    /// Fraction of the quoted price (`0.001` is 10 bps)
+    Fraction(Decimal),
+}
+
+impl Slippage {
+    fn apply(&self, price: Decimal, buying: bool) -> Decimal {
+        let amount = match self {
+            Slippage::None => Decimal::ZERO,
+            Slippage::Fixed(amount) => *amount,
+            Slippage::Fraction(fraction) => price * fraction,
+        };
+        if buying {
+            price + amount
+        } else {
+            (price - amount).max(Decimal::ZERO)
+        }
+    }
+}
+
+/// Commissions and clearing fees charged on simulated fills.
+///
+/// The default approximates tastytrade's published retail schedule.
+#[derive(Debug, Clone)]
+pub struct FeeSchedule {
+    pub equity_per_share: Decimal,
+    pub option_open_per_contract: Decimal,
+    pub option_close_per_contract: Decimal,
+    /// Cap on the per-leg option commission (clearing fees are not capped)
+    pub option_max_commission_per_leg: Option<Decimal>,
+    pub option_clearing_per_contract: Decimal,
+    pub future_per_contract: Decimal,
+    pub future_option_per_contract: Decimal,
+    /// Fraction of notional charged on cryptocurrency trades
+    pub crypto_rate: Decimal,
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self {
+            equity_per_share: Decimal::ZERO,
+            option_open_per_contract: Decimal::ONE,
+            option_close_per_contract: Decimal::ZERO,
+            option_max_commission_per_leg: Some(Decimal::TEN),
+            option_clearing_per_contract: Decimal::new(10, 2),
+            future_per_contract: Decimal::new(125, 2),
+            future_option_per_contract: Decimal::new(250, 2),
+            crypto_rate: Decimal::new(1, 2),
+        }
+    }
+}
+
+impl FeeSchedule {
+    /// A schedule that charges nothing.
+    pub fn zero() -> Self {
+        Self {
+            equity_per_share: Decimal::ZERO,
+            option_open_per_contract: Decimal::ZERO,
+            option_close_per_contract: Decimal::ZERO,
+            option_max_commission_per_leg: None,
+            option_clearing_per_contract: Decimal::ZERO,
+            future_per_contract: Decimal::ZERO,
+            future_option_per_contract: Decimal::ZERO,
+            crypto_rate: Decimal::ZERO,
+        }
+    }
+
+    /// Fees for filling `quantity` of a leg at `price` per unit.
+    pub fn leg_fees(
+        &self,
+        instrument_type: &InstrumentType,
+        action: &Action,
+        quantity: Decimal,
+        price: Decimal,
+        multiplier: Decimal,
+    ) -> Decimal {
+        let quantity = quantity.abs();
+        match instrument_type {
+            InstrumentType::EquityOption => {
+                let rate = if action.is_closing() {
+                    self.option_close_per_contract
+                } else {
+                    self.option_open_per_contract
+                };
+                let commission = rate * quantity;
+                let commission = match self.option_max_commission_per_leg {
+                    Some(cap) => commission.min(cap),
+                    None => commission,
+                };
+                commission + self.option_clearing_per_contract * quantity
+            }
+            InstrumentType::Future => self.future_per_contract * quantity,
+            InstrumentType::FutureOption => self.future_option_per_contract * quantity,
+            InstrumentType::Cryptocurrency => self.crypto_rate * quantity * price * multiplier,
+            _ => self.equity_per_share * quantity,
+        }
+    }
+}
+
+/// Settings for a [`PaperAccount`].
+#[derive(Debug, Clone)]
+pub struct PaperConfig {
+    pub account_number: AccountNumber,
+    pub starting_cash: Decimal,
+    pub slippage: Slippage,
+    pub fees: FeeSchedule,
+    /// Let sells open short positions. No margin is held against them: the
+    /// proceeds are credited to cash and the short is carried in net liq.
+    pub allow_short: bool,
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self {
+            account_number: AccountNumber::from("PAPER"),
+            starting_cash: Decimal::from(100_000),
+            slippage: Slippage::None,
+            fees: FeeSchedule::default(),
+            allow_short: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PaperQuote {
+    bid: Decimal,
+    ask: Decimal,
+}
+
+impl PaperQuote {
+    fn mid(&self) -> Decimal {
+        (self.bid + self.ask) / Decimal::TWO
+    }
+}
+
+#[derive(Debug)]
+struct PaperOrder {
+    record: LiveOrderRecord,
+    order_type: OrderType,
+    time_in_force: TimeInForce,
+    price: Option<Decimal>,
+    stop_trigger: Option<Decimal>,
+    price_effect: PriceEffect,
+    /// Dollar amount for notional orders
+    value: Option<Decimal>,
+    legs: Vec<OrderLeg>,
+    triggered: bool,
+}
+
+#[derive(Debug)]
+struct PaperPosition {
+    instrument_type: InstrumentType,
+    underlying_symbol: Symbol,
+    /// Signed: positive long, negative short
+    quantity: Decimal,
+    average_open_price: Decimal,
+    multiplier: Decimal,
+    realized_today: Decimal,
+    created_at: String,
+    updated_at: String,
+}
+
+#[derive(Debug)]
+struct PaperState {
+    cash: Decimal,
+    quotes: HashMap<Symbol, PaperQuote>,
+    streamer_symbols: HashMap<String, Symbol>,
+    multipliers: HashMap<Symbol, Decimal>,
+    positions: BTreeMap<Symbol, PaperPosition>,
+    orders: BTreeMap<OrderId, PaperOrder>,
+    next_order_id: u64,
+}
+
+/// Per-leg execution prices and the resulting cash flow.
+struct Execution {
+    prices: Vec<Decimal>,
+    /// Filled quantity per leg
+    quantities: Vec<Decimal>,
+    /// Per-unit net price; positive is a debit
+    net_price: Decimal,
+    /// Total cash paid (positive) or received (negative), excluding fees
+    cash_delta: Decimal,
+    fees: Decimal,
+}
+
+/// A simulated account that fills orders locally.
+///
+/// # Example
+/// ```ignore
+/// let paper = PaperAccount::new(PaperConfig {
+///     slippage: Slippage::Fixed(Decimal::new(1, 2)),
+///     ..Default::default()
+/// });
+/// paper.set_quote("AAPL", dec!(189.50), dec!(189.55)).await;
+///
+/// // Any strategy written against `TradingAccount` runs unchanged
+/// run_strategy(&paper).await?;
+/// ```
+#[derive(Debug)]
+pub struct PaperAccount {
+    config: PaperConfig,
+    state: Mutex<PaperState>,
+}
+
+impl PaperAccount {
+    pub fn new(config: PaperConfig) -> Self {
+        let state = PaperState {
+            cash: config.starting_cash,
+            quotes: HashMap::new(),
+            streamer_symbols: HashMap::new(),
+            multipliers: HashMap::new(),
+            positions: BTreeMap::new(),
+            orders: BTreeMap::new(),
+            next_order_id: 1,
+        };
+        Self {
+            config,
+            state: Mutex::new(state),
+        }
+    }
+
+    pub fn config(&self) -> &PaperConfig {
+        &self.config
+    }
+
+    pub async fn cash(&self) -> Decimal {
+        self.state.lock().await.cash
+    }
+
+    /// Override the contract multiplier for a symbol.
+    ///
+    /// Equity options default to 100 and everything else to 1, so futures and
+    /// futures options need their multiplier set here.
+    pub async fn set_multiplier(&self, symbol: impl AsSymbol, multiplier: Decimal) {
+        self.state
+            .lock()
+            .await
+            .multipliers
+            .insert(symbol.as_symbol(), multiplier);
+    }
+
+    /// Route quotes for `streamer_symbol` (e.g. `.AAPL240119C150`) to the order symbol `symbol`.
+    pub async fn map_streamer_symbol(
+        &self,
+        streamer_symbol: impl Into<String>,
+        symbol: impl AsSymbol,
+    ) {
+        self.state
+            .lock()
+            .await
+            .streamer_symbols
+            .insert(streamer_symbol.into(), symbol.as_symbol());
+    }
+
+    /// Update the quote for `symbol` and fill any orders that became executable.
+    pub async fn set_quote(&self, symbol: impl AsSymbol, bid: Decimal, ask: Decimal) {
+        let mut state = self.state.lock().await;
+        state
+            .quotes
+            .insert(symbol.as_symbol(), PaperQuote { bid, ask });
+        self.match_orders(&mut state);
+    }
+
+    /// Apply a quote from the streamer. Quotes missing a bid or ask are ignored.
+    pub async fn apply_quote(&self, quote: &QuoteData) {
+        let (Some(bid), Some(ask)) = (
+            quote.bid_price.and_then(|p| Decimal::try_from(p).ok()),
+            quote.ask_price.and_then(|p| Decimal::try_from(p).ok()),
+        ) else {
+            return;
+        };
+
+        let mut state = self.state.lock().await;
+        let symbol = state
+            .streamer_symbols
+            .get(&quote.symbol)
+            .cloned()
+            .unwrap_or_else(|| Symbol::from(quote.symbol.as_str()));
+        state.quotes.insert(
+            symbol,
+            PaperQuote {
+                bid: bid.normalize(),
+                ask: ask.normalize(),
+            },
+        );
+        self.match_orders(&mut state);
+    }
+
+    /// Apply a quote streamer event; non-quote events are ignored.
+    pub async fn apply_event(&self, event: &StreamerEvent) {
+        if let StreamerEventData::Quote(quote) = &event.data {
+            self.apply_quote(quote).await;
+        }
+    }
+
+    /// Apply a recorded sequence of streamer events in order.
+    pub async fn replay<I>(&self, events: I)
+    where
+        I: IntoIterator<Item = StreamerEvent>,
+    {
+        for event in events {
+            self.apply_event(&event).await;
+        }
+    }
+
+    /// Feed quotes from a live streamer until it stops producing events.
+    pub async fn run_quotes(&self, streamer: &mut DxLinkQuoteStreamer) -> Result<()> {
+        while let Some((_, event)) = streamer.receive_event().await? {
+            self.apply_event(&event).await;
+        }
+        Ok(())
+    }
+
+    fn multiplier(state: &PaperState, leg: &OrderLeg) -> Decimal {
+        if let Some(multiplier) = state.multipliers.get(&leg.symbol) {
+            return *multiplier;
+        }
+        match leg.instrument_type {
+            InstrumentType::EquityOption => Decimal::ONE_HUNDRED,
+            _ => Decimal::ONE,
+        }
+    }
+
+    /// Price every leg at the touch plus slippage, or `None` if a quote is missing.
+    ///
+    /// A single leg without a quantity is sized from the notional `value` at
+    /// its fill price.
+    fn execution(
+        &self,
+        state: &PaperState,
+        legs: &[OrderLeg],
+        value: Option<Decimal>,
+    ) -> Option<Execution> {
+        let notional = match (value, legs) {
+            (Some(value), [leg]) if leg.quantity.is_zero() => Some(value),
+            _ => None,
+        };
+
+        let mut execution = Execution {
+            prices: Vec::with_capacity(legs.len()),
+            quantities: Vec::with_capacity(legs.len()),
+            net_price: Decimal::ZERO,
+            cash_delta: Decimal::ZERO,
+            fees: Decimal::ZERO,
+        };
+        for leg in legs {
+            let quote = state.quotes.get(&leg.symbol)?;
+            let buying = leg.action.is_buy();
+            let touch = if buying { quote.ask } else { quote.bid };
+            let price = self.config.slippage.apply(touch, buying);
+            let sign = if buying {
+                Decimal::ONE
+            } else {
+                Decimal::NEGATIVE_ONE
+            };
+            let multiplier = Self::multiplier(state, leg);
+            let quantity = match notional {
+                Some(value) if !price.is_zero() => {
+                    (value / (price * multiplier)).round_dp(NOTIONAL_QUANTITY_DP)
+                }
+                Some(_) => return None,
+                None => leg.quantity.abs(),
+            };
+
+            // Accumulates the total here; divided into a per-unit price below
+            execution.net_price += sign * price * quantity;
+            execution.cash_delta += sign * price * quantity * multiplier;
+            execution.fees += self.config.fees.leg_fees(
+                &leg.instrument_type,
+                &leg.action,
+                quantity,
+                price,
+                multiplier,
+            );
+            execution.prices.push(price);
+            execution.quantities.push(quantity);
+        }
+
+        let units = execution
+            .quantities
+            .iter()
+            .copied()
+            .min()
+            .filter(|units| !units.is_zero())?;
+        execution.net_price /= units;
+        Some(execution)
+    }
+
+    /// The first sell that would take a position short, if shorting is not allowed.
+    fn uncovered_sell<'a>(
+        &self,
+        state: &PaperState,
+        legs: &'a [OrderLeg],
+        quantities: &[Decimal],
+    ) -> Option<&'a Symbol> {
+        if self.config.allow_short {
+            return None;
+        }
+        legs.iter()
+            .zip(quantities)
+            .find(|(leg, quantity)| {
+                let held = state
+                    .positions
+                    .get(&leg.symbol)
+                    .map_or(Decimal::ZERO, |p| p.quantity.max(Decimal::ZERO));
+                !leg.action.is_buy() && **quantity > held
+            })
+            .map(|(leg, _)| &leg.symbol)
+    }
+
+    fn reject(state: &mut PaperState, id: OrderId) {
+        if let Some(order) = state.orders.get_mut(&id) {
+            order.record.status = OrderStatus::Rejected;
+            order.record.cancellable = false;
+            order.record.editable = false;
+        }
+    }
+
+    /// Whether a limit at `limit` accepts a per-unit net price of `net_price` (positive = debit).
+    fn limit_accepts(limit: Decimal, price_effect: &PriceEffect, net_price: Decimal) -> bool {
+        match price_effect {
+            PriceEffect::Credit => -net_price >= limit,
+            PriceEffect::Debit => net_price <= limit,
+            PriceEffect::None if net_price.is_sign_negative() => -net_price >= limit,
+            PriceEffect::None => net_price <= limit,
+        }
+    }
+
+    fn stop_triggered(state: &PaperState, order: &PaperOrder) -> bool {
+        let (Some(trigger), Some(leg)) = (order.stop_trigger, order.legs.first()) else {
+            return false;
+        };
+        let Some(quote) = state.quotes.get(&leg.symbol) else {
+            return false;
+        };
+        if leg.action.is_buy() {
+            quote.ask >= trigger
+        } else {
+            quote.bid <= trigger
+        }
+    }
+
+    /// Fill the order if it is executable at the current quotes, returning the fees charged.
+    fn try_fill(&self, state: &mut PaperState, id: OrderId) -> Option<Decimal> {
+        let order = state.orders.get(&id)?;
+        if !order.record.status.is_working() {
+            return None;
+        }
+
+        let triggered = order.triggered || Self::stop_triggered(state, order);
+        let execution = self.execution(state, &order.legs, order.value)?;
+        let executable = match order.order_type {
+            OrderType::Market | OrderType::NotionalMarket => true,
+            OrderType::Limit | OrderType::MarketableLimit => order.price.is_some_and(|limit| {
+                Self::limit_accepts(limit, &order.price_effect, execution.net_price)
+            }),
+            OrderType::Stop => triggered,
+            OrderType::StopLimit => {
+                triggered
+                    && order.price.is_some_and(|limit| {
+                        Self::limit_accepts(limit, &order.price_effect, execution.net_price)
+                    })
+            }
+        };
+
+        if triggered {
+            if let Some(order) = state.orders.get_mut(&id) {
+                order.triggered = true;
+            }
+        }
+        if !executable {
+            return None;
+        }
+
+        let legs = state.orders[&id].legs.clone();
+        if let Some(symbol) = self.uncovered_sell(state, &legs, &execution.quantities) {
+            debug!(
+                "Paper order {:?} rejected: selling more {} than held",
+                id, symbol.0
+            );
+            Self::reject(state, id);
+            return None;
+        }
+
+        let total = execution.cash_delta + execution.fees;
+        if total > state.cash {
+            debug!("Paper order {:?} rejected: insufficient cash", id);
+            Self::reject(state, id);
+            return None;
+        }
+
+        state.cash -= total;
+        for ((leg, price), quantity) in legs
+            .iter()
+            .zip(&execution.prices)
+            .zip(&execution.quantities)
+        {
+            let multiplier = Self::multiplier(state, leg);
+            let underlying = state.orders[&id].record.underlying_symbol.clone();
+            apply_fill(state, leg, *price, *quantity, multiplier, underlying);
+        }
+
+        if let Some(order) = state.orders.get_mut(&id) {
+            debug!("Paper order {:?} filled at {}", id, execution.net_price);
+            order.record.status = OrderStatus::Filled;
+            order.record.price = execution.net_price.abs();
+            order.record.cancellable = false;
+            order.record.editable = false;
+        }
+        Some(execution.fees)
+    }
+
+    fn match_orders(&self, state: &mut PaperState) {
+        let working: Vec<OrderId> = state
+            .orders
+            .iter()
+            .filter(|(_, order)| order.record.status.is_working())
+            .map(|(id, _)| *id)
+            .collect();
+        for id in working {
+            self.try_fill(state, id);
+        }
+    }
+
+    fn mark(state: &PaperState, symbol: &Symbol, position: &PaperPosition) -> Decimal {
+        state
+            .quotes
+            .get(symbol)
+            .map(PaperQuote::mid)
+            .unwrap_or(position.average_open_price)
+    }
+
+    fn validate(order: &Order) -> std::result::Result<(), PaperTradingError> {
+        if order.legs.is_empty() {
+            return Err(PaperTradingError::EmptyOrder);
+        }
+        match order.order_type {
+            OrderType::Limit | OrderType::MarketableLimit | OrderType::StopLimit
+                if order.price.is_none() =>
+            {
+                Err(PaperTradingError::MissingPrice(order.order_type.clone()))
+            }
+            OrderType::Stop | OrderType::StopLimit if order.stop_trigger.is_none() => Err(
+                PaperTradingError::MissingStopTrigger(order.order_type.clone()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    fn record(&self, id: OrderId, order: &Order, status: OrderStatus) -> LiveOrderRecord {
+        let size = order
+            .legs
+            .iter()
+            .map(|leg| leg.quantity.abs())
+            .min()
+            .and_then(|quantity| quantity.trunc().to_u64())
+            .unwrap_or(0);
+        LiveOrderRecord {
+            id,
+            account_number: self.config.account_number.clone(),
+            time_in_force: order.time_in_force.clone(),
+            order_type: order.order_type.clone(),
+            size,
+            underlying_symbol: order
+                .legs
+                .first()
+                .map(|leg| leg.symbol.root())
+                .unwrap_or_else(|| Symbol::from("")),
+            price: order.price.unwrap_or_default(),
+            price_effect: order.price_effect.clone(),
+            status,
+            cancellable: true,
+            editable: true,
+            edited: false,
+            received_at: Some(Utc::now()),
+            legs: order
+                .legs
+                .iter()
+                .map(|leg| LiveOrderLeg {
+                    instrument_type: leg.instrument_type.clone(),
+                    symbol: leg.symbol.clone(),
+                    quantity: leg.quantity,
+                    remaining_quantity: leg.quantity,
+                    action: leg.action.clone(),
+                    fills: vec![],
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TradingAccount for PaperAccount {
+    fn number(&self) -> AccountNumber {
+        self.config.account_number.clone()
+    }
+
+    async fn balance(&self) -> Result<Balance> {
+        let state = self.state.lock().await;
+        let mut long_equity = Decimal::ZERO;
+        let mut short_equity = Decimal::ZERO;
+        let mut long_derivative = Decimal::ZERO;
+        let mut short_derivative = Decimal::ZERO;
+        let mut long_futures = Decimal::ZERO;
+        let mut short_futures = Decimal::ZERO;
+
+        for (symbol, position) in &state.positions {
+            let value =
+                position.quantity * Self::mark(&state, symbol, position) * position.multiplier;
+            let (long, short) = match position.instrument_type {
+                InstrumentType::EquityOption | InstrumentType::FutureOption => {
+                    (&mut long_derivative, &mut short_derivative)
+                }
+                InstrumentType::Future => (&mut long_futures, &mut short_futures),
+                _ => (&mut long_equity, &mut short_equity),
+            };
+            if value.is_sign_negative() {
+                *short += -value;
+            } else {
+                *long += value;
+            }
+        }
+
+        // Without margin, every position (futures included) is carried at full notional
+        let net_liquidating_value = state.cash + long_equity - short_equity + long_derivative
+            - short_derivative
+            + long_futures
+            - short_futures;
+        let zero = Decimal::ZERO;
+        Ok(Balance {
+            account_number: self.config.account_number.clone(),
+            cash_balance: state.cash,
+            long_equity_value: long_equity,
+            short_equity_value: short_equity,
+            long_derivative_value: long_derivative,
+            short_derivative_value: short_derivative,
+            long_futures_value: long_futures,
+            short_futures_value: short_futures,
+            long_futures_derivative_value: zero,
+            short_futures_derivative_value: zero,
+            long_margineable_value: zero,
+            short_margineable_value: zero,
+            margin_equity: net_liquidating_value,
+            equity_buying_power: state.cash,
+            derivative_buying_power: state.cash,
+            day_trading_buying_power: zero,
+            futures_margin_requirement: zero,
+            available_trading_funds: state.cash,
+            maintenance_requirement: zero,
+            maintenance_call_value: zero,
+            reg_t_call_value: zero,
+            day_trading_call_value: zero,
+            day_equity_call_value: zero,
+            net_liquidating_value,
+            cash_available_to_withdraw: state.cash,
+            day_trade_excess: zero,
+            pending_cash: zero,
+            pending_cash_effect: PriceEffect::None,
+            pending_margin_interest: zero,
+            effective_cryptocurrency_buying_power: state.cash,
+            updated_at: Utc::now().to_rfc3339(),
+        })
+    }
+
+    async fn positions(&self) -> Result<Vec<FullPosition>> {
+        let state = self.state.lock().await;
+        Ok(state
+            .positions
+            .iter()
+            .filter(|(_, position)| !position.quantity.is_zero())
+            .map(|(symbol, position)| {
+                let long = position.quantity.is_sign_positive();
+                let realized_effect = if position.realized_today.is_sign_negative() {
+                    "Debit"
+                } else {
+                    "Credit"
+                };
+                FullPosition {
+                    account_number: self.config.account_number.clone(),
+                    symbol: symbol.clone(),
+                    instrument_type: position.instrument_type.clone(),
+                    underlying_symbol: position.underlying_symbol.clone(),
+                    quantity: position.quantity.abs(),
+                    quantity_direction: if long {
+                        QuantityDirection::Long
+                    } else {
+                        QuantityDirection::Short
+                    },
+                    close_price: Self::mark(&state, symbol, position),
+                    average_open_price: position.average_open_price,
+                    average_yearly_market_close_price: position.average_open_price,
+                    average_daily_market_close_price: position.average_open_price,
+                    multiplier: position.multiplier,
+                    cost_effect: if long {
+                        PriceEffect::Debit
+                    } else {
+                        PriceEffect::Credit
+                    },
+                    is_suppressed: false,
+                    is_frozen: false,
+                    restricted_quantity: Decimal::ZERO,
+                    realized_day_gain: position.realized_today.abs(),
+                    realized_day_gain_effect: realized_effect.to_string(),
+                    realized_day_gain_date: Utc::now().date_naive().to_string(),
+                    realized_today: position.realized_today.abs(),
+                    realized_today_effect: realized_effect.to_string(),
+                    realized_today_date: Utc::now().date_naive().to_string(),
+                    created_at: position.created_at.clone(),
+                    updated_at: position.updated_at.clone(),
+                }
+            })
+            .collect())
+    }
+
+    async fn live_orders(&self) -> Result<Vec<LiveOrderRecord>> {
+        let state = self.state.lock().await;
+        Ok(state
+            .orders
+            .values()
+            .map(|order| order.record.clone())
+            .collect())
+    }
+
+    async fn dry_run(&self, order: &Order) -> Result<DryRunResult> {
+        Self::validate(order)?;
+        let state = self.state.lock().await;
+
+        let (cash_delta, fees) = match self.execution(&state, &order.legs, order.value) {
+            Some(execution) => (execution.cash_delta, execution.fees),
+            None => (Decimal::ZERO, Decimal::ZERO),
+        };
+        let change = cash_delta + fees;
+        let effect = |value: Decimal| {
+            if value.is_sign_negative() {
+                PriceEffect::Credit
+            } else {
+                PriceEffect::Debit
+            }
+        };
+        let record = self.record(OrderId(0), order, OrderStatus::Received);
+
+        Ok(DryRunResult {
+            order: DryRunRecord {
+                account_number: record.account_number,
+                time_in_force: record.time_in_force,
+                order_type: record.order_type,
+                size: record.size,
+                underlying_symbol: record.underlying_symbol,
+                price: record.price,
+                price_effect: record.price_effect,
+                status: record.status,
+                cancellable: record.cancellable,
+                editable: record.editable,
+                edited: record.edited,
+                legs: order.legs.clone(),
+            },
+            warnings: vec![],
+            buying_power_effect: BuyingPowerEffect {
+                change_in_margin_requirement: Decimal::ZERO,
+                change_in_margin_requirement_effect: PriceEffect::None,
+                change_in_buying_power: change.abs(),
+                change_in_buying_power_effect: effect(change),
+                current_buying_power: state.cash,
+                current_buying_power_effect: PriceEffect::Credit,
+                impact: change.abs(),
+                effect: effect(change),
+            },
+            fee_calculation: FeeCalculation {
+                total_fees: fees,
+                total_fees_effect: PriceEffect::Debit,
+            },
+        })
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderPlacedResult> {
+        Self::validate(order)?;
+        let mut state = self.state.lock().await;
+
+        let id = OrderId(state.next_order_id);
+        state.next_order_id += 1;
+        state.orders.insert(
+            id,
+            PaperOrder {
+                record: self.record(id, order, OrderStatus::Live),
+                order_type: order.order_type.clone(),
+                time_in_force: order.time_in_force.clone(),
+                price: order.price,
+                stop_trigger: order.stop_trigger,
+                price_effect: order.price_effect.clone(),
+                value: order.value,
+                legs: order.legs.clone(),
+                triggered: false,
+            },
+        );
+
+        let cash_before = state.cash;
+        let fees = self.try_fill(&mut state, id).unwrap_or_default();
+
+        let paper_order = state
+            .orders
+            .get_mut(&id)
+            .ok_or(PaperTradingError::UnknownOrder(id))?;
+        if matches!(paper_order.time_in_force, TimeInForce::IOC)
+            && paper_order.record.status.is_working()
+        {
+            paper_order.record.status = OrderStatus::Cancelled;
+            paper_order.record.cancellable = false;
+            paper_order.record.editable = false;
+        }
+        let record = paper_order.record.clone();
+
+        let change = cash_before - state.cash;
+        Ok(OrderPlacedResult {
+            order: record,
+            warnings: vec![],
+            buying_power_effect: BuyingPowerEffect {
+                change_in_margin_requirement: Decimal::ZERO,
+                change_in_margin_requirement_effect: PriceEffect::None,
+                change_in_buying_power: change.abs(),
+                change_in_buying_power_effect: if change.is_sign_negative() {
+                    PriceEffect::Credit
+                } else {
+                    PriceEffect::Debit
+                },
+                current_buying_power: state.cash,
+                current_buying_power_effect: PriceEffect::Credit,
+                impact: change.abs(),
+                effect: PriceEffect::None,
+            },
+            fee_calculation: FeeCalculation {
+                total_fees: fees,
+                total_fees_effect: PriceEffect::Debit,
+            },
+        })
+    }
+
+    async fn cancel_order(&self, id: OrderId) -> Result<LiveOrderRecord> {
+        let mut state = self.state.lock().await;
+        let order = state
+            .orders
+            .get_mut(&id)
+            .ok_or(PaperTradingError::UnknownOrder(id))?;
+        if !order.record.status.is_working() {
+            return Err(PaperTradingError::NotCancellable {
+                id,
+                status: order.record.status,
+            }
+            .into());
+        }
+        order.record.status = OrderStatus::Cancelled;
+        order.record.cancellable = false;
+        order.record.editable = false;
+        Ok(order.record.clone())
+    }
+}
+
+/// Update the position for a filled leg, realizing P&L on any reduced quantity.
+fn apply_fill(
+    state: &mut PaperState,
+    leg: &OrderLeg,
+    price: Decimal,
+    quantity: Decimal,
+    multiplier: Decimal,
+    underlying: Symbol,
+) {
+    let now = Utc::now().to_rfc3339();
+    let delta = if leg.action.is_buy() {
+        quantity
+    } else {
+        -quantity
+    };
+    let position = state
+        .positions
+        .entry(leg.symbol.clone())
+        .or_insert_with(|| PaperPosition {
+            instrument_type: leg.instrument_type.clone(),
+            underlying_symbol: underlying,
+            quantity: Decimal::ZERO,
+            average_open_price: price,
+            multiplier,
+            realized_today: Decimal::ZERO,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        });
+
+    let old = position.quantity;
+    let new = old + delta;
+    if old.is_zero() || old.is_sign_negative() == delta.is_sign_negative() {
+        // Opening or adding
+        position.average_open_price =
+            (old.abs() * position.average_open_price + delta.abs() * price) / new.abs();
+    } else {
+        let closed = delta.abs().min(old.abs());
+        let direction = if old.is_sign_positive() {
+            Decimal::ONE
+        } else {
+            Decimal::NEGATIVE_ONE
+        };
+        position.realized_today +=
+            (price - position.average_open_price) * closed * multiplier * direction;
+        if !new.is_zero() && new.is_sign_negative() != old.is_sign_negative() {
+            // Flipped through flat; the remainder opens at the fill price
+            position.average_open_price = price;
+        }
+    }
+    position.quantity = new;
+    position.updated_at = now;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::order::{OrderBuilder, OrderLegBuilder};
+    use crate::api::test_util::dec;
+
+    fn leg(symbol: &str, instrument_type: InstrumentType, qty: i64, action: Action) -> OrderLeg {
+        OrderLegBuilder::default()
+            .instrument_type(instrument_type)
+            .symbol(symbol)
+            .quantity(Decimal::from(qty))
+            .action(action)
+            .build()
+            .unwrap()
+    }
+
+    fn order(
+        order_type: OrderType,
+        price: Option<&str>,
+        effect: PriceEffect,
+        legs: Vec<OrderLeg>,
+    ) -> Order {
+        let mut builder = OrderBuilder::default();
+        builder
+            .time_in_force(TimeInForce::Day)
+            .order_type(order_type)
+            .price_effect(effect)
+            .legs(legs);
+        if let Some(p) = price {
+            builder.price(dec(p));
+        }
+        builder.build().unwrap()
+    }
+
+    fn paper(fees: FeeSchedule, slippage: Slippage) -> PaperAccount {
+        PaperAccount::new(PaperConfig {
+            starting_cash: dec("10000"),
+            slippage,
+            fees,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_market_order_fills_at_touch_with_slippage() {
+        let paper = paper(FeeSchedule::zero(), Slippage::Fixed(dec("0.05")));
+        paper.set_quote("AAPL", dec("100.00"), dec("100.10")).await;
+
+        let placed = paper
+            .place_order(&order(
+                OrderType::Market,
+                None,
+                PriceEffect::Debit,
+                vec![leg("AAPL", InstrumentType::Equity, 10, Action::Buy)],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(placed.order.status, OrderStatus::Filled);
+        assert_eq!(placed.order.price, dec("100.15"));
+        assert_eq!(paper.cash().await, dec("8998.50"));
+
+        let positions = paper.positions().await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].quantity, dec("10"));
+        assert_eq!(positions[0].average_open_price, dec("100.15"));
+        assert!(matches!(
+            positions[0].quantity_direction,
+            QuantityDirection::Long
+        ));
+
+        // Marked at mid: 10 * 100.05
+        let balance = paper.balance().await.unwrap();
+        assert_eq!(balance.long_equity_value, dec("1000.50"));
+        assert_eq!(balance.net_liquidating_value, dec("9999.00"));
+    }
+
+    #[tokio::test]
+    async fn test_limit_order_rests_until_quote_crosses() {
+        let paper = paper(FeeSchedule::zero(), Slippage::None);
+        paper.set_quote("SPY", dec("450.00"), dec("450.10")).await;
+
+        let placed = paper
+            .place_order(&order(
+                OrderType::Limit,
+                Some("449.50"),
+                PriceEffect::Debit,
+                vec![leg("SPY", InstrumentType::Equity, 1, Action::Buy)],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(placed.order.status, OrderStatus::Live);
+
+        paper.set_quote("SPY", dec("449.40"), dec("449.50")).await;
+        let orders = paper.live_orders().await.unwrap();
+        assert_eq!(orders[0].status, OrderStatus::Filled);
+        assert_eq!(paper.cash().await, dec("9550.50"));
+    }
+
+    #[tokio::test]
+    async fn test_credit_spread_with_option_fees_and_multiplier() {
+        let paper = PaperAccount::new(PaperConfig {
+            starting_cash: dec("10000"),
+            allow_short: true,
+            ..Default::default()
+        });
+        paper
+            .set_quote("SPY   240119P00450000", dec("5.00"), dec("5.10"))
+            .await;
+        paper
+            .set_quote("SPY   240119P00440000", dec("3.00"), dec("3.10"))
+            .await;
+
+        let placed = paper
+            .place_order(&order(
+                OrderType::Limit,
+                Some("1.80"),
+                PriceEffect::Credit,
+                vec![
+                    leg(
+                        "SPY   240119P00450000",
+                        InstrumentType::EquityOption,
+                        2,
+                        Action::SellToOpen,
+                    ),
+                    leg(
+                        "SPY   240119P00440000",
+                        InstrumentType::EquityOption,
+                        2,
+                        Action::BuyToOpen,
+                    ),
+                ],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(placed.order.status, OrderStatus::Filled);
+        assert_eq!(placed.order.price, dec("1.90"));
+        assert_eq!(placed.fee_calculation.total_fees, dec("4.40"));
+        // Credit 1.90 * 2 * 100 = 380, fees 2 legs * 2 contracts * (1.00 + 0.10) = 4.40
+        assert_eq!(paper.cash().await, dec("10375.60"));
+        assert_eq!(paper.positions().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_closing_realizes_pnl() {
+        let paper = paper(FeeSchedule::zero(), Slippage::None);
+        paper.set_quote("AAPL", dec("100"), dec("100")).await;
+        paper
+            .place_order(&order(
+                OrderType::Market,
+                None,
+                PriceEffect::Debit,
+                vec![leg("AAPL", InstrumentType::Equity, 10, Action::Buy)],
+            ))
+            .await
+            .unwrap();
+        paper.set_quote("AAPL", dec("105"), dec("105")).await;
+        paper
+            .place_order(&order(
+                OrderType::Market,
+                None,
+                PriceEffect::Credit,
+                vec![leg("AAPL", InstrumentType::Equity, 4, Action::Sell)],
+            ))
+            .await
+            .unwrap();
+
+        let positions = paper.positions().await.unwrap();
+        assert_eq!(positions[0].quantity, dec("6"));
+        assert_eq!(positions[0].realized_today, dec("20"));
+        assert_eq!(positions[0].average_open_price, dec("100"));
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_cash_rejects_and_cancel() {
+        let paper = paper(FeeSchedule::zero(), Slippage::None);
+        paper.set_quote("AMZN", dec("200"), dec("200")).await;
+        let placed = paper
+            .place_order(&order(
+                OrderType::Market,
+                None,
+                PriceEffect::Debit,
+                vec![leg("AMZN", InstrumentType::Equity, 100, Action::Buy)],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(placed.order.status, OrderStatus::Rejected);
+
+        let resting = paper
+            .place_order(&order(
+                OrderType::Limit,
+                Some("150"),
+                PriceEffect::Debit,
+                vec![leg("AMZN", InstrumentType::Equity, 1, Action::Buy)],
+            ))
+            .await
+            .unwrap();
+        let cancelled = paper.cancel_order(resting.order.id).await.unwrap();
+        assert_eq!(cancelled.status, OrderStatus::Cancelled);
+        assert!(paper.cancel_order(resting.order.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_notional_crypto_order_fills_by_value() {
+        let paper = paper(FeeSchedule::zero(), Slippage::None);
+        paper.set_quote("BTC/USD", dec("49990"), dec("50000")).await;
+
+        let notional = Order::crypto_notional("BTC/USD", dec("250"), Action::BuyToOpen).unwrap();
+        let placed = paper.place_order(&notional).await.unwrap();
+        assert_eq!(placed.order.status, OrderStatus::Filled);
+        assert_eq!(placed.order.price, dec("50000"));
+        assert_eq!(paper.cash().await, dec("9750"));
+
+        let positions = paper.positions().await.unwrap();
+        assert_eq!(positions[0].quantity, dec("0.005"));
+
+        // Fractional quantities are reported exactly on the order legs
+        let tenth = OrderLeg::crypto("BTC/USD", dec("0.1"), Action::BuyToOpen).unwrap();
+        let placed = paper
+            .place_order(&order(
+                OrderType::Market,
+                None,
+                PriceEffect::Debit,
+                vec![tenth],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(placed.order.status, OrderStatus::Filled);
+        assert_eq!(placed.order.legs[0].quantity, dec("0.1"));
+        assert_eq!(paper.positions().await.unwrap()[0].quantity, dec("0.105"));
+    }
+
+    #[tokio::test]
+    async fn test_cash_account_rejects_uncovered_sells() {
+        let paper = paper(FeeSchedule::zero(), Slippage::None);
+        paper.set_quote("AAPL", dec("100"), dec("100")).await;
+        paper
+            .place_order(&order(
+                OrderType::Market,
+                None,
+                PriceEffect::Debit,
+                vec![leg("AAPL", InstrumentType::Equity, 5, Action::Buy)],
+            ))
+            .await
+            .unwrap();
+
+        let sell = |qty| {
+            order(
+                OrderType::Market,
+                None,
+                PriceEffect::Credit,
+                vec![leg("AAPL", InstrumentType::Equity, qty, Action::Sell)],
+            )
+        };
+        let placed = paper.place_order(&sell(6)).await.unwrap();
+        assert_eq!(placed.order.status, OrderStatus::Rejected);
+        assert_eq!(paper.cash().await, dec("9500"));
+
+        let placed = paper.place_order(&sell(5)).await.unwrap();
+        assert_eq!(placed.order.status, OrderStatus::Filled);
+        assert!(paper.positions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replayed_streamer_quotes_fill_stop_order() {
+        let paper = paper(FeeSchedule::zero(), Slippage::None);
+        paper.set_quote("TSLA", dec("250"), dec("250.10")).await;
+        paper
+            .place_order(&order(
+                OrderType::Market,
+                None,
+                PriceEffect::Debit,
+                vec![leg("TSLA", InstrumentType::Equity, 1, Action::Buy)],
+            ))
+            .await
+            .unwrap();
+
+        let mut builder = OrderBuilder::default();
+        builder
+            .time_in_force(TimeInForce::GTC)
+            .order_type(OrderType::Stop)
+            .price_effect(PriceEffect::Credit)
+            .stop_trigger(dec("240"))
+            .legs(vec![leg("TSLA", InstrumentType::Equity, 1, Action::Sell)]);
+        let stop = paper.place_order(&builder.build().unwrap()).await.unwrap();
+        assert_eq!(stop.order.status, OrderStatus::Live);
+
+        let quote = |bid: f64, ask: f64| StreamerEvent {
+            event_type: "Quote".to_string(),
+            data: StreamerEventData::Quote(QuoteData {
+                symbol: "TSLA".to_string(),
+                bid_price: Some(bid),
+                ask_price: Some(ask),
+                bid_size: None,
+                ask_size: None,
+                event_time: None,
+                day_volume: None,
+            }),
+        };
+        paper
+            .replay(vec![quote(245.0, 245.1), quote(239.5, 239.6)])
+            .await;
+
+        let orders = paper.live_orders().await.unwrap();
+        assert_eq!(orders[1].status, OrderStatus::Filled);
+        assert!(paper.positions().await.unwrap().is_empty());
+    }
+}
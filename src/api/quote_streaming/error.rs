@@ -47,6 +47,7 @@ impl From<QuoteStreamingError> for crate::api::base::TastyError {
             code: Some(code.to_string()),
             message,
             errors: None,
+            status: None,
         })
     }
 }
@@ -0,0 +1,508 @@
+//! Walking a working limit order toward the mid.
+//!
+//! [`reprice`] places a limit order, then every [`RepricerConfig::interval`]
+//! replaces it one [`RepricerConfig::step`] closer to (and possibly through)
+//! the current mid, never conceding more than
+//! [`RepricerConfig::max_concession`] past it. It stops as soon as the
+//! [`OrderTracker`] reports the order terminal.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use tracing::debug;
+
+use crate::api::accounts::Account;
+use crate::api::base::{Result, TastyError};
+use crate::api::market_data::{MarketDataParam, MarketDataRequest};
+use crate::api::order_tracker::OrderTracker;
+use crate::api::order_validation::TickSchedule;
+use crate::api::quote_streaming::QuoteData;
+use crate::TastyTrade;
+
+use super::order::{
+    InstrumentType, LiveOrderRecord, Order, OrderId, OrderLeg, PriceEffect, Symbol,
+};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RepricerError {
+    #[error("order has no limit price to start from")]
+    MissingPrice,
+    #[error("order must be a debit or a credit to be repriced")]
+    MissingPriceEffect,
+}
+
+/// What to do once the price has reached the maximum concession without filling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepriceFallback {
+    /// Cancel the working order
+    Cancel,
+    /// Leave the order working at its last price
+    Hold,
+}
+
+#[derive(Debug, Clone)]
+pub struct RepricerConfig {
+    /// Price change per replace
+    pub step: Decimal,
+    /// Time between replaces
+    pub interval: Duration,
+    /// How far past the mid the price may go
+    pub max_concession: Decimal,
+    pub fallback: RepriceFallback,
+    /// Tick schedule to keep prices on; rounding always favours the trader
+    pub tick_schedule: Option<TickSchedule>,
+}
+
+impl RepricerConfig {
+    pub fn new(step: Decimal, interval: Duration) -> Self {
+        Self {
+            step,
+            interval,
+            max_concession: Decimal::ZERO,
+            fallback: RepriceFallback::Cancel,
+            tick_schedule: None,
+        }
+    }
+
+    pub fn with_max_concession(mut self, max_concession: Decimal) -> Self {
+        self.max_concession = max_concession;
+        self
+    }
+
+    pub fn with_fallback(mut self, fallback: RepriceFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    pub fn with_tick_schedule(mut self, tick_schedule: TickSchedule) -> Self {
+        self.tick_schedule = Some(tick_schedule);
+        self
+    }
+
+    /// The next price after `current`, or `None` once no further concession is allowed.
+    ///
+    /// Prices and `mid` are quoted as the order quotes them: a positive debit
+    /// to pay or a positive credit to receive. With a tick schedule, each step
+    /// concedes at least one tick.
+    pub fn next_price(&self, current: Decimal, mid: Decimal, credit: bool) -> Option<Decimal> {
+        let (limit, rounding) = if credit {
+            (
+                (mid - self.max_concession).max(Decimal::ZERO),
+                RoundingStrategy::AwayFromZero,
+            )
+        } else {
+            (mid + self.max_concession, RoundingStrategy::ToZero)
+        };
+        let concedes = |price: Decimal| {
+            if credit {
+                price < current && price >= limit
+            } else {
+                price > current && price <= limit
+            }
+        };
+
+        let next = if credit {
+            (current - self.step).max(limit)
+        } else {
+            (current + self.step).min(limit)
+        };
+        let next = match &self.tick_schedule {
+            Some(schedule) => {
+                let rounded = schedule.round(next, rounding);
+                if concedes(rounded) {
+                    rounded
+                } else if credit {
+                    // A step smaller than the tick rounds back to `current`;
+                    // move a whole tick (of the tier below, at a threshold) instead
+                    let tick = schedule.tick_for(current - schedule.tick_for(current));
+                    schedule.round(current - tick, rounding)
+                } else {
+                    schedule.round(current + schedule.tick_for(current), rounding)
+                }
+            }
+            None => next,
+        };
+        concedes(next).then_some(next)
+    }
+}
+
+/// A source of per-unit net mid prices for an order.
+///
+/// The mid is signed like a cash flow: positive for a net debit, negative for
+/// a net credit.
+pub trait MidSource {
+    fn net_mid(&mut self, order: &Order) -> impl Future<Output = Result<Option<Decimal>>> + Send;
+}
+
+/// Net mid of `legs` from per-symbol mids, with leg quantities taken as ratios.
+fn net_mid<F>(legs: &[OrderLeg], mut mid_for: F) -> Option<Decimal>
+where
+    F: FnMut(&Symbol) -> Option<Decimal>,
+{
+    let units = legs.iter().map(|leg| leg.quantity.abs()).min()?;
+    if units.is_zero() {
+        return None;
+    }
+    let mut net = Decimal::ZERO;
+    for leg in legs {
+        let mid = mid_for(&leg.symbol)?;
+        let ratio = leg.quantity.abs() / units;
+        if leg.action.is_buy() {
+            net += mid * ratio;
+        } else {
+            net -= mid * ratio;
+        }
+    }
+    Some(net)
+}
+
+/// Mids from the REST market data endpoint.
+pub struct MarketDataMid<'t> {
+    tasty: &'t TastyTrade,
+}
+
+impl<'t> MarketDataMid<'t> {
+    pub fn new(tasty: &'t TastyTrade) -> Self {
+        Self { tasty }
+    }
+}
+
+impl MidSource for MarketDataMid<'_> {
+    async fn net_mid(&mut self, order: &Order) -> Result<Option<Decimal>> {
+        let mut request = MarketDataRequest::new();
+        for leg in &order.legs {
+            let param = match leg.instrument_type {
+                InstrumentType::Equity => MarketDataParam::Equity,
+                InstrumentType::EquityOption => MarketDataParam::EquityOption,
+                InstrumentType::Future => MarketDataParam::Future,
+                InstrumentType::FutureOption => MarketDataParam::FutureOption,
+                InstrumentType::Cryptocurrency => MarketDataParam::Cryptocurrency,
+                InstrumentType::Index => MarketDataParam::Index,
+                _ => return Ok(None),
+            };
+            request.add_symbol(param, leg.symbol.0.clone());
+        }
+
+        let mids: HashMap<String, Decimal> = self
+            .tasty
+            .fetch_market_data(&request)
+            .await?
+            .into_iter()
+            .filter_map(|item| {
+                let mid = item.mid.or(match (item.bid, item.ask) {
+                    (Some(bid), Some(ask)) => Some((bid + ask) / Decimal::TWO),
+                    _ => None,
+                })?;
+                Some((item.symbol, mid))
+            })
+            .collect();
+        Ok(net_mid(&order.legs, |symbol| mids.get(&symbol.0).copied()))
+    }
+}
+
+/// Mids from streamed quotes.
+///
+/// Feed it events from a
+/// [`DxLinkQuoteStreamer`](crate::api::quote_streaming::DxLinkQuoteStreamer)
+/// with [`QuoteBook::update`]. Option quotes arrive under their streamer
+/// symbol, so register the mapping with [`QuoteBook::map_streamer_symbol`].
+///
+/// Clones share the same quotes, so one clone can be fed from the streamer
+/// while another is handed to [`reprice`].
+#[derive(Debug, Clone, Default)]
+pub struct QuoteBook {
+    inner: Arc<RwLock<QuoteBookInner>>,
+}
+
+#[derive(Debug, Default)]
+struct QuoteBookInner {
+    mids: HashMap<Symbol, Decimal>,
+    streamer_symbols: HashMap<String, Symbol>,
+}
+
+impl QuoteBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn map_streamer_symbol(&self, streamer_symbol: impl Into<String>, symbol: Symbol) {
+        self.inner
+            .write()
+            .unwrap()
+            .streamer_symbols
+            .insert(streamer_symbol.into(), symbol);
+    }
+
+    /// Record the mid of `quote`. Quotes missing a bid or ask are ignored.
+    pub fn update(&self, quote: &QuoteData) {
+        let (Some(bid), Some(ask)) = (
+            quote.bid_price.and_then(|p| Decimal::try_from(p).ok()),
+            quote.ask_price.and_then(|p| Decimal::try_from(p).ok()),
+        ) else {
+            return;
+        };
+        let mut inner = self.inner.write().unwrap();
+        let symbol = inner
+            .streamer_symbols
+            .get(&quote.symbol)
+            .cloned()
+            .unwrap_or_else(|| Symbol::from(quote.symbol.as_str()));
+        inner
+            .mids
+            .insert(symbol, ((bid + ask) / Decimal::TWO).normalize());
+    }
+
+    pub fn mid(&self, symbol: &Symbol) -> Option<Decimal> {
+        self.inner.read().unwrap().mids.get(symbol).copied()
+    }
+}
+
+impl MidSource for QuoteBook {
+    async fn net_mid(&mut self, order: &Order) -> Result<Option<Decimal>> {
+        Ok(net_mid(&order.legs, |symbol| self.mid(symbol)))
+    }
+}
+
+/// How a repricing run ended.
+#[derive(Debug)]
+pub enum RepriceOutcome {
+    Filled(LiveOrderRecord),
+    /// The order ended without filling (e.g. cancelled elsewhere or rejected)
+    Ended(LiveOrderRecord),
+    /// Maximum concession reached and the order was cancelled
+    Cancelled(LiveOrderRecord),
+    /// Maximum concession reached and the order was left working
+    Holding(LiveOrderRecord),
+}
+
+fn with_price(order: &Order, price: Decimal) -> Order {
+    Order {
+        price: Some(price),
+        ..order.clone()
+    }
+}
+
+fn terminal_outcome(record: LiveOrderRecord) -> RepriceOutcome {
+    if record.status == super::order::OrderStatus::Filled {
+        RepriceOutcome::Filled(record)
+    } else {
+        RepriceOutcome::Ended(record)
+    }
+}
+
+/// The outcome of an order that reached a terminal state (e.g. filled)
+/// before a replace or cancel got to it, or `error` if it is still working.
+async fn terminal_or(
+    tracker: &OrderTracker,
+    id: &OrderId,
+    error: TastyError,
+) -> Result<RepriceOutcome> {
+    match tracker.get(id).await {
+        Some(latest) if latest.status.is_terminal() => Ok(terminal_outcome(latest)),
+        _ => Err(error),
+    }
+}
+
+/// Place `order` and walk its limit price toward the mid until it fills or
+/// the maximum concession is reached.
+///
+/// `tracker` must be receiving order updates for `account` (see
+/// [`OrderTracker::run`]) for fills to be noticed. Intervals where `mids`
+/// has no mid are skipped.
+pub async fn reprice<M: MidSource>(
+    account: &Account<'_>,
+    order: &Order,
+    tracker: &OrderTracker,
+    mids: &mut M,
+    config: &RepricerConfig,
+) -> Result<RepriceOutcome> {
+    let mut price = order.price.ok_or(RepricerError::MissingPrice)?;
+    let credit = match order.price_effect {
+        PriceEffect::Credit => true,
+        PriceEffect::Debit => false,
+        PriceEffect::None => return Err(RepricerError::MissingPriceEffect.into()),
+    };
+
+    let mut record = account.place_order(order).await?.order;
+    tracker.apply(record.clone()).await;
+
+    loop {
+        tokio::select! {
+            done = tracker.await_terminal(record.id) => return Ok(terminal_outcome(done)),
+            _ = tokio::time::sleep(config.interval) => {}
+        }
+
+        let Some(net) = mids.net_mid(order).await? else {
+            continue;
+        };
+        let mid = if credit { -net } else { net };
+
+        match config.next_price(price, mid, credit) {
+            Some(next) => {
+                debug!("Repricing order {:?} from {} to {}", record.id, price, next);
+                match account
+                    .replace_order(record.id, &with_price(order, next))
+                    .await
+                {
+                    Ok(replaced) => {
+                        record = replaced;
+                        price = next;
+                        tracker.apply(record.clone()).await;
+                    }
+                    // The order may have filled while we were replacing it
+                    Err(e) => return terminal_or(tracker, &record.id, e).await,
+                }
+            }
+            None => {
+                return match config.fallback {
+                    RepriceFallback::Cancel => match account.cancel_order(record.id).await {
+                        Ok(cancelled) => {
+                            tracker.apply(cancelled.clone()).await;
+                            Ok(RepriceOutcome::Cancelled(cancelled))
+                        }
+                        // Likewise, it may have filled just before the cancel
+                        Err(e) => terminal_or(tracker, &record.id, e).await,
+                    },
+                    RepriceFallback::Hold => Ok(RepriceOutcome::Holding(record)),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::order::{Action, OrderBuilder, OrderLegBuilder, OrderType, TimeInForce};
+    use crate::api::test_util::dec;
+
+    fn config() -> RepricerConfig {
+        RepricerConfig::new(dec("0.05"), Duration::from_secs(1)).with_max_concession(dec("0.05"))
+    }
+
+    #[test]
+    fn test_debit_steps_up_to_mid_plus_concession() {
+        let config = config();
+        assert_eq!(
+            config.next_price(dec("1.00"), dec("1.10"), false),
+            Some(dec("1.05"))
+        );
+        assert_eq!(
+            config.next_price(dec("1.12"), dec("1.10"), false),
+            Some(dec("1.15"))
+        );
+        assert_eq!(config.next_price(dec("1.15"), dec("1.10"), false), None);
+    }
+
+    #[test]
+    fn test_credit_steps_down_to_mid_minus_concession() {
+        let config = config();
+        assert_eq!(
+            config.next_price(dec("2.00"), dec("1.90"), true),
+            Some(dec("1.95"))
+        );
+        assert_eq!(
+            config.next_price(dec("1.88"), dec("1.90"), true),
+            Some(dec("1.85"))
+        );
+        assert_eq!(config.next_price(dec("1.85"), dec("1.90"), true), None);
+        // Never flips a credit into a debit
+        assert_eq!(
+            config.next_price(dec("0.02"), dec("0.01"), true),
+            Some(dec("0"))
+        );
+    }
+
+    #[test]
+    fn test_ticks_round_in_traders_favour() {
+        let config = RepricerConfig::new(dec("0.07"), Duration::from_secs(1))
+            .with_max_concession(dec("1"))
+            .with_tick_schedule(TickSchedule::standard_option());
+        // 3.00 + 0.07 = 3.07 -> 3.05 on the nickel tier
+        assert_eq!(
+            config.next_price(dec("3.00"), dec("3.50"), false),
+            Some(dec("3.05"))
+        );
+        // Credit 3.50 - 0.07 = 3.43 -> 3.45
+        assert_eq!(
+            config.next_price(dec("3.50"), dec("3.00"), true),
+            Some(dec("3.45"))
+        );
+    }
+
+    #[test]
+    fn test_step_smaller_than_tick_moves_a_whole_tick() {
+        let config = RepricerConfig::new(dec("0.01"), Duration::from_secs(1))
+            .with_max_concession(dec("1"))
+            .with_tick_schedule(TickSchedule::standard_option());
+        // 3.00 + 0.01 rounds back to 3.00 on the nickel tier
+        assert_eq!(
+            config.next_price(dec("3.00"), dec("3.50"), false),
+            Some(dec("3.05"))
+        );
+        assert_eq!(
+            config.next_price(dec("3.50"), dec("3.00"), true),
+            Some(dec("3.45"))
+        );
+        // Stepping down across the threshold moves one penny
+        assert_eq!(
+            config.next_price(dec("3.00"), dec("2.50"), true),
+            Some(dec("2.99"))
+        );
+
+        // The whole tick would pass the cap, so there is nothing left to concede
+        let capped = RepricerConfig::new(dec("0.01"), Duration::from_secs(1))
+            .with_max_concession(dec("0.02"))
+            .with_tick_schedule(TickSchedule::standard_option());
+        assert_eq!(capped.next_price(dec("3.00"), dec("3.00"), false), None);
+    }
+
+    #[tokio::test]
+    async fn test_quote_book_net_mid_for_spread() {
+        let book = QuoteBook::new();
+        book.map_streamer_symbol(".SPY240119P450", Symbol::from("SPY   240119P00450000"));
+        let quote = |symbol: &str, bid: f64, ask: f64| QuoteData {
+            symbol: symbol.to_string(),
+            bid_price: Some(bid),
+            ask_price: Some(ask),
+            bid_size: None,
+            ask_size: None,
+            event_time: None,
+            day_volume: None,
+        };
+        book.update(&quote(".SPY240119P450", 5.0, 5.2));
+        book.update(&quote("SPY   240119P00440000", 3.0, 3.1));
+
+        let leg = |symbol: &str, action: Action| {
+            OrderLegBuilder::default()
+                .instrument_type(InstrumentType::EquityOption)
+                .symbol(symbol)
+                .quantity(Decimal::ONE)
+                .action(action)
+                .build()
+                .unwrap()
+        };
+        let order = OrderBuilder::default()
+            .time_in_force(TimeInForce::Day)
+            .order_type(OrderType::Limit)
+            .price(dec("2.10"))
+            .price_effect(PriceEffect::Credit)
+            .legs(vec![
+                leg("SPY   240119P00450000", Action::SellToOpen),
+                leg("SPY   240119P00440000", Action::BuyToOpen),
+            ])
+            .build()
+            .unwrap();
+
+        // Sell 5.10 mid, buy 3.05 mid -> net credit 2.05
+        let mut shared = book.clone();
+        assert_eq!(shared.net_mid(&order).await.unwrap(), Some(dec("-2.05")));
+
+        let order = with_price(&order, dec("2.00"));
+        assert_eq!(order.price, Some(dec("2.00")));
+        assert_eq!(order.legs.len(), 2);
+    }
+}
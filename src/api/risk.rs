@@ -0,0 +1,670 @@
+//! Opt-in pre-trade risk checks.
+//!
+//! A [`RiskGuard`] installed with [`TastyTrade::set_risk_guard`] is consulted by
+//! [`Account::place_order`] and [`Account::replace_order`] before anything is
+//! sent to the API. Orders that breach a limit fail with
+//! [`TastyError::RiskRejected`](crate::api::base::TastyError::RiskRejected).
+//!
+//! [`TastyTrade::set_risk_guard`]: crate::TastyTrade::set_risk_guard
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use chrono::{DateTime, Datelike, Days, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use rust_decimal::Decimal;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::api::accounts::{Account, AccountNumber, Balance, BalanceSnapshot, SnapshotTimeOfDay};
+use crate::api::base::Result;
+
+use super::future_symbol::FutureOptionSymbol;
+use super::futures::{Future, FutureOptionExpiration};
+use super::order::{AsSymbol, InstrumentType, Order, OrderId, OrderLeg, Symbol};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RiskViolation {
+    #[error("kill switch is engaged")]
+    KillSwitch,
+    #[error("order notional {notional} exceeds limit {limit}")]
+    MaxNotional { notional: Decimal, limit: Decimal },
+    #[error("order notional cannot be computed without a price")]
+    UnpricedOrder,
+    #[error("order quantity cannot be checked on an order sized by value")]
+    UnsizedOrder,
+    #[error("contract multiplier for {0} is unknown")]
+    UnknownMultiplier(String),
+    #[error("order quantity {quantity} exceeds limit {limit}")]
+    MaxContracts { quantity: Decimal, limit: Decimal },
+    #[error("{open} open orders already working (limit {limit})")]
+    MaxOpenOrders { open: usize, limit: usize },
+    #[error("symbol {0} is not on the allow list")]
+    SymbolNotAllowed(String),
+    #[error("symbol {0} is on the deny list")]
+    SymbolDenied(String),
+    #[error("daily loss {loss} has reached the limit {limit}")]
+    DailyLoss { loss: Decimal, limit: Decimal },
+    #[error("no start-of-day balance for account {0}")]
+    NoDayStart(String),
+}
+
+/// Limits enforced by a [`RiskGuard`]. Every limit is optional; `None` disables it.
+#[derive(Debug, Clone, Default)]
+pub struct RiskLimits {
+    /// Maximum `|price| * quantity * multiplier` for a single order
+    pub max_notional: Option<Decimal>,
+    /// Maximum total leg quantity for a single order. Orders sized by value
+    /// have no quantity to check and are rejected while this is set.
+    pub max_contracts: Option<Decimal>,
+    /// Maximum number of working orders in the account before a new one is placed
+    pub max_open_orders: Option<usize>,
+    /// If non-empty, only these symbols (or option roots) may be traded
+    pub allowed_symbols: BTreeSet<Symbol>,
+    /// Symbols (or option roots) that may never be traded
+    pub denied_symbols: BTreeSet<Symbol>,
+    /// Stop trading once net liquidating value has fallen this far since the
+    /// start of the trading day (see [`trading_date`])
+    pub max_daily_loss: Option<Decimal>,
+}
+
+/// Pre-trade risk guard shared by every account on a client.
+///
+/// # Example
+/// ```ignore
+/// let guard = Arc::new(RiskGuard::new(RiskLimits {
+///     max_notional: Some(Decimal::from(25_000)),
+///     max_contracts: Some(Decimal::from(10)),
+///     ..Default::default()
+/// }));
+/// tasty.set_risk_guard(Some(guard.clone())).await;
+///
+/// // Later, from anywhere:
+/// guard.engage_kill_switch();
+/// ```
+#[derive(Debug)]
+pub struct RiskGuard {
+    limits: RiskLimits,
+    kill_switch: AtomicBool,
+    day_start: Mutex<BTreeMap<AccountNumber, (NaiveDate, Decimal)>>,
+    multipliers: RwLock<BTreeMap<Symbol, Decimal>>,
+}
+
+impl RiskGuard {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self {
+            limits,
+            kill_switch: AtomicBool::new(false),
+            day_start: Mutex::new(BTreeMap::new()),
+            multipliers: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Reject every order until [`release_kill_switch`](Self::release_kill_switch) is called.
+    pub fn engage_kill_switch(&self) {
+        warn!("Risk guard kill switch engaged");
+        self.kill_switch.store(true, Ordering::SeqCst);
+    }
+
+    pub fn release_kill_switch(&self) {
+        self.kill_switch.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_killed(&self) -> bool {
+        self.kill_switch.load(Ordering::SeqCst)
+    }
+
+    /// Record the dollar value of a one-point move for a futures contract
+    /// (`/ESZ4`) or futures option contract code (`EW4U4`).
+    ///
+    /// Futures and futures option legs without a known multiplier are
+    /// rejected by the notional limit. [`check`](Self::check) looks futures
+    /// up on its own; futures options have to be registered, e.g. with
+    /// [`register_future_options`](Self::register_future_options).
+    pub fn set_multiplier(&self, symbol: impl AsSymbol, multiplier: Decimal) {
+        self.multipliers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(symbol.as_symbol(), multiplier);
+    }
+
+    pub fn register_future(&self, future: &Future) {
+        self.set_multiplier(&future.symbol, future.notional_multiplier);
+    }
+
+    /// Register the multiplier of every option in a futures option expiration.
+    pub fn register_future_options(&self, expiration: &FutureOptionExpiration) {
+        self.set_multiplier(
+            expiration.option_contract_symbol.as_str(),
+            expiration.notional_value,
+        );
+    }
+
+    /// Record `balance` as the baseline for the daily loss stop on the
+    /// current [`trading_date`].
+    ///
+    /// [`check`](Self::check) falls back to the previous close from the
+    /// account's end-of-day balance snapshots; [`check_balance`](Self::check_balance)
+    /// on its own rejects every order until a baseline is set.
+    pub async fn set_day_start(&self, balance: &Balance) {
+        self.day_start.lock().await.insert(
+            balance.account_number.clone(),
+            (trading_date(Utc::now()), balance.net_liquidating_value),
+        );
+    }
+
+    /// Checks that only need the order itself: kill switch, symbols, size and notional.
+    pub fn check_order(&self, order: &Order) -> std::result::Result<(), RiskViolation> {
+        if self.is_killed() {
+            return Err(RiskViolation::KillSwitch);
+        }
+
+        for leg in &order.legs {
+            self.check_symbol(&leg.symbol)?;
+        }
+
+        let quantity: Decimal = order.legs.iter().map(|leg| leg.quantity.abs()).sum();
+        if let Some(limit) = self.limits.max_contracts {
+            // A notional order's quantity is only known once it fills
+            if order.value.is_some() {
+                return Err(RiskViolation::UnsizedOrder);
+            }
+            if quantity > limit {
+                return Err(RiskViolation::MaxContracts { quantity, limit });
+            }
+        }
+
+        if let Some(limit) = self.limits.max_notional {
+            let notional = self.order_notional(order)?;
+            if notional > limit {
+                return Err(RiskViolation::MaxNotional { notional, limit });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check the daily loss stop against a fresh balance.
+    pub async fn check_balance(&self, balance: &Balance) -> std::result::Result<(), RiskViolation> {
+        let Some(limit) = self.limits.max_daily_loss else {
+            return Ok(());
+        };
+
+        let today = trading_date(Utc::now());
+        let baseline = match self.day_start.lock().await.get(&balance.account_number) {
+            Some((date, value)) if *date == today => *value,
+            _ => return Err(RiskViolation::NoDayStart(balance.account_number.0.clone())),
+        };
+
+        let loss = baseline - balance.net_liquidating_value;
+        if loss >= limit {
+            return Err(RiskViolation::DailyLoss { loss, limit });
+        }
+        Ok(())
+    }
+
+    /// Run every configured check for `order` on `account`.
+    ///
+    /// `replacing` is the order being replaced, if any; it is not counted
+    /// towards the open order limit.
+    pub async fn check(
+        &self,
+        account: &Account<'_>,
+        order: &Order,
+        replacing: Option<&OrderId>,
+    ) -> Result<()> {
+        // Checked before any request so an engaged switch is never masked by a network error
+        if self.is_killed() {
+            return Err(RiskViolation::KillSwitch.into());
+        }
+        if self.limits.max_notional.is_some() {
+            for leg in &order.legs {
+                if matches!(leg.instrument_type, InstrumentType::Future)
+                    && self.multiplier(&leg.symbol).is_none()
+                {
+                    let future = account.client().get_future(&leg.symbol).await?;
+                    self.register_future(&future);
+                }
+            }
+        }
+        self.check_order(order)?;
+
+        if let Some(limit) = self.limits.max_open_orders {
+            if replacing.is_none() {
+                let open = account
+                    .live_orders()
+                    .await?
+                    .iter()
+                    .filter(|o| o.status.is_working())
+                    .count();
+                if open >= limit {
+                    return Err(RiskViolation::MaxOpenOrders { open, limit }.into());
+                }
+            }
+        }
+
+        if self.limits.max_daily_loss.is_some() {
+            self.ensure_day_start(account).await?;
+            let balance = account.balance().await?;
+            self.check_balance(&balance).await?;
+        }
+
+        Ok(())
+    }
+
+    fn check_symbol(&self, symbol: &Symbol) -> std::result::Result<(), RiskViolation> {
+        let root = symbol.root();
+        let listed = |set: &BTreeSet<Symbol>| set.contains(symbol) || set.contains(&root);
+
+        if listed(&self.limits.denied_symbols) {
+            return Err(RiskViolation::SymbolDenied(symbol.0.clone()));
+        }
+        if !self.limits.allowed_symbols.is_empty() && !listed(&self.limits.allowed_symbols) {
+            return Err(RiskViolation::SymbolNotAllowed(symbol.0.clone()));
+        }
+        Ok(())
+    }
+
+    /// Use the previous close as the baseline if none was set for today.
+    async fn ensure_day_start(&self, account: &Account<'_>) -> Result<()> {
+        let number = account.number();
+        let today = trading_date(Utc::now());
+        if matches!(self.day_start.lock().await.get(&number), Some((date, _)) if *date == today) {
+            return Ok(());
+        }
+
+        let week_ago = today.checked_sub_days(Days::new(7)).unwrap_or(today);
+        let snapshots = account
+            .balance_snapshot(week_ago, today, SnapshotTimeOfDay::EOD, 0)
+            .await?;
+        if let Some(close) = previous_close(&snapshots.items, today) {
+            self.day_start.lock().await.insert(number, (today, close));
+        }
+        Ok(())
+    }
+
+    fn multiplier(&self, key: &Symbol) -> Option<Decimal> {
+        self.multipliers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .copied()
+    }
+
+    fn leg_multiplier(&self, leg: &OrderLeg) -> std::result::Result<Decimal, RiskViolation> {
+        match leg.instrument_type {
+            InstrumentType::Equity
+            | InstrumentType::EquityOffering
+            | InstrumentType::Warrant
+            | InstrumentType::Cryptocurrency => Some(Decimal::ONE),
+            InstrumentType::EquityOption => Some(Decimal::ONE_HUNDRED),
+            InstrumentType::Future => self.multiplier(&leg.symbol),
+            // The multiplier belongs to the option contract code (`EW4U4`)
+            InstrumentType::FutureOption => FutureOptionSymbol::parse(&leg.symbol.0)
+                .ok()
+                .and_then(|option| self.multiplier(&Symbol::from(option.option_contract()))),
+            _ => None,
+        }
+        .ok_or_else(|| RiskViolation::UnknownMultiplier(leg.symbol.0.clone()))
+    }
+
+    /// Conservative notional estimate: the order price (or stop trigger) times
+    /// the largest leg quantity and multiplier, or the dollar value of an
+    /// order sized by value.
+    fn order_notional(&self, order: &Order) -> std::result::Result<Decimal, RiskViolation> {
+        if let Some(value) = order.value {
+            return Ok(value.abs());
+        }
+        let price = order
+            .price
+            .or(order.stop_trigger)
+            .ok_or(RiskViolation::UnpricedOrder)?;
+        let quantity = order
+            .legs
+            .iter()
+            .map(|leg| leg.quantity.abs())
+            .max()
+            .unwrap_or(Decimal::ZERO);
+        let mut multiplier = Decimal::ONE;
+        for leg in &order.legs {
+            multiplier = multiplier.max(self.leg_multiplier(leg)?);
+        }
+        Ok(price.abs() * quantity * multiplier)
+    }
+}
+
+/// The trading date the daily loss stop is counted against: the calendar
+/// date in New York, so the day rolls over at midnight US Eastern time rather
+/// than at midnight UTC.
+pub fn trading_date(now: DateTime<Utc>) -> NaiveDate {
+    let year = now.year();
+    // US daylight saving time runs from 2:00 local on the second Sunday of
+    // March to 2:00 local on the first Sunday of November
+    let dst_start = nth_sunday(year, 3, 2).and_hms_opt(7, 0, 0);
+    let dst_end = nth_sunday(year, 11, 1).and_hms_opt(6, 0, 0);
+    let in_dst = match (dst_start, dst_end) {
+        (Some(start), Some(end)) => {
+            now >= Utc.from_utc_datetime(&start) && now < Utc.from_utc_datetime(&end)
+        }
+        _ => false,
+    };
+    let offset = if in_dst { 4 } else { 5 };
+    (now - Duration::hours(offset)).date_naive()
+}
+
+fn nth_sunday(year: i32, month: u32, n: u32) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n as u8)
+        .expect("every month has at least four Sundays")
+}
+
+/// Net liquidating value at the latest end-of-day snapshot before `today`.
+fn previous_close(snapshots: &[BalanceSnapshot], today: NaiveDate) -> Option<Decimal> {
+    snapshots
+        .iter()
+        .filter(|s| s.snapshot_date < today)
+        .max_by_key(|s| s.snapshot_date)
+        .map(|s| s.net_liquidating_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::order::{
+        Action, OrderBuilder, OrderLegBuilder, OrderType, PriceEffect, TimeInForce,
+    };
+    use crate::api::test_util::dec;
+    use serde_json::json;
+
+    fn order(
+        symbol: &str,
+        instrument_type: InstrumentType,
+        qty: i64,
+        price: Option<&str>,
+    ) -> Order {
+        let leg = OrderLegBuilder::default()
+            .instrument_type(instrument_type)
+            .symbol(symbol)
+            .quantity(Decimal::from(qty))
+            .action(Action::BuyToOpen)
+            .build()
+            .unwrap();
+        let mut builder = OrderBuilder::default();
+        builder
+            .time_in_force(TimeInForce::Day)
+            .order_type(OrderType::Limit)
+            .price_effect(PriceEffect::Debit)
+            .legs(vec![leg]);
+        if let Some(p) = price {
+            builder.price(dec(p));
+        }
+        builder.build().unwrap()
+    }
+
+    fn balance(net_liq: &str) -> Balance {
+        serde_json::from_value(balance_json(net_liq)).unwrap()
+    }
+
+    fn balance_json(net_liq: &str) -> serde_json::Value {
+        let mut value = json!({
+            "account-number": "ACC123",
+            "pending-cash-effect": "None",
+            "updated-at": "2023-01-01T12:00:00Z"
+        });
+        for field in [
+            "cash-balance",
+            "long-equity-value",
+            "short-equity-value",
+            "long-derivative-value",
+            "short-derivative-value",
+            "long-futures-value",
+            "short-futures-value",
+            "long-futures-derivative-value",
+            "short-futures-derivative-value",
+            "long-margineable-value",
+            "short-margineable-value",
+            "margin-equity",
+            "equity-buying-power",
+            "derivative-buying-power",
+            "day-trading-buying-power",
+            "futures-margin-requirement",
+            "available-trading-funds",
+            "maintenance-requirement",
+            "maintenance-call-value",
+            "reg-t-call-value",
+            "day-trading-call-value",
+            "day-equity-call-value",
+            "cash-available-to-withdraw",
+            "day-trade-excess",
+            "pending-cash",
+            "pending-margin-interest",
+            "effective-cryptocurrency-buying-power",
+        ] {
+            value[field] = json!("0.00");
+        }
+        value["net-liquidating-value"] = json!(net_liq);
+        value
+    }
+
+    #[test]
+    fn test_default_limits_allow_everything() {
+        let guard = RiskGuard::new(RiskLimits::default());
+        assert!(guard
+            .check_order(&order("AAPL", InstrumentType::Equity, 1_000_000, None))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_kill_switch() {
+        let guard = RiskGuard::new(RiskLimits::default());
+        guard.engage_kill_switch();
+        assert!(matches!(
+            guard.check_order(&order("AAPL", InstrumentType::Equity, 1, Some("1.00"))),
+            Err(RiskViolation::KillSwitch)
+        ));
+        guard.release_kill_switch();
+        assert!(!guard.is_killed());
+    }
+
+    #[test]
+    fn test_max_notional_uses_option_multiplier() {
+        let guard = RiskGuard::new(RiskLimits {
+            max_notional: Some(dec("1000")),
+            ..Default::default()
+        });
+        // 2 contracts * $4.50 * 100 = $900
+        assert!(guard
+            .check_order(&order(
+                "AAPL  240119C00150000",
+                InstrumentType::EquityOption,
+                2,
+                Some("4.50")
+            ))
+            .is_ok());
+        // 3 contracts * $4.50 * 100 = $1350
+        match guard.check_order(&order(
+            "AAPL  240119C00150000",
+            InstrumentType::EquityOption,
+            3,
+            Some("4.50"),
+        )) {
+            Err(RiskViolation::MaxNotional { notional, limit }) => {
+                assert_eq!(notional, dec("1350"));
+                assert_eq!(limit, dec("1000"));
+            }
+            other => panic!("Expected MaxNotional, got {:?}", other),
+        }
+        assert!(matches!(
+            guard.check_order(&order("AAPL", InstrumentType::Equity, 1, None)),
+            Err(RiskViolation::UnpricedOrder)
+        ));
+    }
+
+    #[test]
+    fn test_max_notional_uses_futures_multipliers() {
+        let guard = RiskGuard::new(RiskLimits {
+            max_notional: Some(dec("100000")),
+            ..Default::default()
+        });
+        let es = order("/ESZ4", InstrumentType::Future, 1, Some("5000"));
+        let es_option = order(
+            "./ESZ4 EW4U4 241018C5800",
+            InstrumentType::FutureOption,
+            1,
+            Some("40"),
+        );
+        assert!(matches!(
+            guard.check_order(&es),
+            Err(RiskViolation::UnknownMultiplier(_))
+        ));
+        assert!(matches!(
+            guard.check_order(&es_option),
+            Err(RiskViolation::UnknownMultiplier(_))
+        ));
+
+        // 1 contract * 5000 points * $50 = $250,000
+        guard.set_multiplier("/ESZ4", dec("50"));
+        match guard.check_order(&es) {
+            Err(RiskViolation::MaxNotional { notional, .. }) => {
+                assert_eq!(notional, dec("250000"))
+            }
+            other => panic!("Expected MaxNotional, got {:?}", other),
+        }
+        // 1 contract * 40 points * $50 = $2,000
+        guard.set_multiplier("EW4U4", dec("50"));
+        assert!(guard.check_order(&es_option).is_ok());
+    }
+
+    #[test]
+    fn test_notional_orders_are_limited_by_value() {
+        let btc = Order::crypto_notional("BTC/USD", dec("250"), Action::BuyToOpen).unwrap();
+        let guard = RiskGuard::new(RiskLimits {
+            max_notional: Some(dec("1000")),
+            ..Default::default()
+        });
+        assert!(guard.check_order(&btc).is_ok());
+        let large = Order::crypto_notional("BTC/USD", dec("1500"), Action::BuyToOpen).unwrap();
+        match guard.check_order(&large) {
+            Err(RiskViolation::MaxNotional { notional, .. }) => assert_eq!(notional, dec("1500")),
+            other => panic!("Expected MaxNotional, got {:?}", other),
+        }
+
+        let guard = RiskGuard::new(RiskLimits {
+            max_contracts: Some(dec("1")),
+            ..Default::default()
+        });
+        assert!(matches!(
+            guard.check_order(&btc),
+            Err(RiskViolation::UnsizedOrder)
+        ));
+    }
+
+    #[test]
+    fn test_max_contracts() {
+        let guard = RiskGuard::new(RiskLimits {
+            max_contracts: Some(dec("5")),
+            ..Default::default()
+        });
+        assert!(guard
+            .check_order(&order("SPY", InstrumentType::Equity, 5, Some("1.00")))
+            .is_ok());
+        assert!(matches!(
+            guard.check_order(&order("SPY", InstrumentType::Equity, 6, Some("1.00"))),
+            Err(RiskViolation::MaxContracts { .. })
+        ));
+    }
+
+    #[test]
+    fn test_symbol_allow_and_deny_lists_match_option_roots() {
+        let guard = RiskGuard::new(RiskLimits {
+            allowed_symbols: [Symbol::from("SPY"), Symbol::from("AAPL")].into(),
+            denied_symbols: [Symbol::from("AAPL  240119C00150000")].into(),
+            ..Default::default()
+        });
+        assert!(guard
+            .check_order(&order(
+                "SPY   240119P00450000",
+                InstrumentType::EquityOption,
+                1,
+                Some("1")
+            ))
+            .is_ok());
+        assert!(matches!(
+            guard.check_order(&order("TSLA", InstrumentType::Equity, 1, Some("1"))),
+            Err(RiskViolation::SymbolNotAllowed(_))
+        ));
+        assert!(matches!(
+            guard.check_order(&order(
+                "AAPL  240119C00150000",
+                InstrumentType::EquityOption,
+                1,
+                Some("1")
+            )),
+            Err(RiskViolation::SymbolDenied(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_daily_loss_stop() {
+        let guard = RiskGuard::new(RiskLimits {
+            max_daily_loss: Some(dec("500")),
+            ..Default::default()
+        });
+        // Without a baseline nothing can be traded
+        assert!(matches!(
+            guard.check_balance(&balance("10000")).await,
+            Err(RiskViolation::NoDayStart(_))
+        ));
+
+        guard.set_day_start(&balance("10000")).await;
+        assert!(guard.check_balance(&balance("9600")).await.is_ok());
+        match guard.check_balance(&balance("9500")).await {
+            Err(RiskViolation::DailyLoss { loss, .. }) => assert_eq!(loss, dec("500")),
+            other => panic!("Expected DailyLoss, got {:?}", other),
+        }
+
+        guard.set_day_start(&balance("9500")).await;
+        assert!(guard.check_balance(&balance("9400")).await.is_ok());
+    }
+
+    #[test]
+    fn test_trading_date_rolls_over_in_new_york() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        // 03:30 UTC is still the previous evening in New York (EST and EDT)
+        assert_eq!(trading_date(at("2024-01-10T03:30:00Z")), date(2024, 1, 9));
+        assert_eq!(trading_date(at("2024-07-10T03:30:00Z")), date(2024, 7, 9));
+        assert_eq!(trading_date(at("2024-07-10T04:30:00Z")), date(2024, 7, 10));
+        assert_eq!(trading_date(at("2024-01-10T04:30:00Z")), date(2024, 1, 9));
+        assert_eq!(trading_date(at("2024-01-10T05:30:00Z")), date(2024, 1, 10));
+    }
+
+    #[test]
+    fn test_previous_close_uses_latest_snapshot_before_today() {
+        let snapshot = |date: &str, net_liq: &str| -> BalanceSnapshot {
+            let mut value = balance_json(net_liq);
+            value["snapshot-date"] = json!(date);
+            serde_json::from_value(value).unwrap()
+        };
+        let snapshots = [
+            snapshot("2024-02-01", "9000"),
+            snapshot("2024-02-02", "9500"),
+            snapshot("2024-02-05", "9900"),
+        ];
+        let today = NaiveDate::from_ymd_opt(2024, 2, 5).unwrap();
+        assert_eq!(previous_close(&snapshots, today), Some(dec("9500")));
+        assert_eq!(previous_close(&snapshots[..0], today), None);
+    }
+
+    #[test]
+    fn test_risk_violation_converts_to_tasty_error() {
+        let err: crate::api::base::TastyError = RiskViolation::KillSwitch.into();
+        assert!(matches!(
+            err,
+            crate::api::base::TastyError::RiskRejected(RiskViolation::KillSwitch)
+        ));
+    }
+}
@@ -0,0 +1,421 @@
+//! Rolling option positions.
+//!
+//! [`plan_roll`] picks the contract to roll an equity option position into
+//! from a [`NestedOptionChain`], and [`RollPlan::order`] turns the plan into a
+//! single two-leg limit [`Order`] that closes the current contract and opens
+//! the new one.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::instrument::OptionType;
+use super::option_chain::{Expiration, NestedOptionChain, Strike};
+use super::order::{
+    Action, InstrumentType, Order, OrderBuilder, OrderLegBuilder, OrderType, PriceEffect, Symbol,
+    TimeInForce,
+};
+use super::position::{FullPosition, QuantityDirection};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RollError {
+    #[error("{} is not an equity option position", .0.0)]
+    NotAnOption(Symbol),
+    #[error("position in {} is flat", .0.0)]
+    FlatPosition(Symbol),
+    #[error("{} was not found in the option chain", .0.0)]
+    ContractNotInChain(Symbol),
+    #[error("no expiration in the chain matches the roll target")]
+    NoExpiration,
+    #[error("no strike in expiration {0} matches the roll target")]
+    NoStrike(NaiveDate),
+    #[error("no deltas available for expiration {0}")]
+    MissingDelta(NaiveDate),
+    #[error("roll target is the contract already held")]
+    SameContract,
+}
+
+/// Which expiration to roll into.
+#[derive(Debug, Clone)]
+pub enum RollExpiration {
+    /// Stay in the current expiration
+    Same,
+    /// The first expiration after the current one
+    Next,
+    /// The first expiration at least this many days out
+    MinDays(u64),
+    /// A specific expiration date
+    Date(NaiveDate),
+}
+
+/// Which strike to roll into, within the target expiration.
+#[derive(Debug, Clone)]
+pub enum RollStrike {
+    /// The current strike price
+    Same,
+    /// This many listed strikes above (positive) or below (negative) the current strike
+    Offset(i64),
+    /// The contract whose delta is closest to `current`.
+    ///
+    /// `deltas` maps candidate contract symbols to their delta, e.g. collected
+    /// from streamer greeks.
+    Delta {
+        current: f64,
+        deltas: HashMap<Symbol, f64>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct RollTarget {
+    pub expiration: RollExpiration,
+    pub strike: RollStrike,
+}
+
+impl RollTarget {
+    /// Same strike, next expiration: the classic calendar roll.
+    pub fn next_expiration() -> Self {
+        Self {
+            expiration: RollExpiration::Next,
+            strike: RollStrike::Same,
+        }
+    }
+}
+
+/// The contracts and actions for a roll.
+#[derive(Debug, Clone)]
+pub struct RollPlan {
+    pub close_symbol: Symbol,
+    pub close_action: Action,
+    pub open_symbol: Symbol,
+    pub open_action: Action,
+    pub quantity: Decimal,
+    pub expiration_date: NaiveDate,
+    pub strike_price: Decimal,
+}
+
+impl RollPlan {
+    /// Build the two-leg roll order as a day limit order.
+    ///
+    /// `close_price` and `open_price` are per-share prices (e.g. mids) for the
+    /// current and target contracts; the net of the two becomes the limit
+    /// price and decides whether the roll is a credit or a debit.
+    pub fn order(&self, close_price: Decimal, open_price: Decimal) -> Order {
+        let net_credit = if self.close_action.is_buy() {
+            open_price - close_price
+        } else {
+            close_price - open_price
+        };
+        let price_effect = if net_credit.is_sign_negative() {
+            PriceEffect::Debit
+        } else {
+            PriceEffect::Credit
+        };
+
+        let leg = |symbol: &Symbol, action: &Action| {
+            OrderLegBuilder::default()
+                .instrument_type(InstrumentType::EquityOption)
+                .symbol(symbol.clone())
+                .quantity(self.quantity)
+                .action(action.clone())
+                .build()
+                .expect("all order leg fields are set")
+        };
+
+        OrderBuilder::default()
+            .time_in_force(TimeInForce::Day)
+            .order_type(OrderType::Limit)
+            .price(net_credit.abs())
+            .price_effect(price_effect)
+            .legs(vec![
+                leg(&self.close_symbol, &self.close_action),
+                leg(&self.open_symbol, &self.open_action),
+            ])
+            .build()
+            .expect("all order fields are set")
+    }
+}
+
+/// Choose the contract to roll `position` into.
+pub fn plan_roll(
+    position: &FullPosition,
+    chain: &NestedOptionChain,
+    target: &RollTarget,
+) -> Result<RollPlan, RollError> {
+    if !matches!(position.instrument_type, InstrumentType::EquityOption) {
+        return Err(RollError::NotAnOption(position.symbol.clone()));
+    }
+    let (close_action, open_action) = match position.quantity_direction {
+        QuantityDirection::Long => (Action::SellToClose, Action::BuyToOpen),
+        QuantityDirection::Short => (Action::BuyToClose, Action::SellToOpen),
+        QuantityDirection::Zero => return Err(RollError::FlatPosition(position.symbol.clone())),
+    };
+    if position.quantity.is_zero() {
+        return Err(RollError::FlatPosition(position.symbol.clone()));
+    }
+
+    let (current_expiration, current_strike, is_call) = chain
+        .expirations
+        .iter()
+        .find_map(|expiration| {
+            expiration.strikes.iter().find_map(|strike| {
+                if strike.call == position.symbol {
+                    Some((expiration, strike, true))
+                } else if strike.put == position.symbol {
+                    Some((expiration, strike, false))
+                } else {
+                    None
+                }
+            })
+        })
+        .ok_or_else(|| RollError::ContractNotInChain(position.symbol.clone()))?;
+
+    let expiration = select_expiration(chain, current_expiration, &target.expiration)?;
+    let strike = select_strike(expiration, current_strike, is_call, &target.strike)?;
+    let open_symbol = if is_call { &strike.call } else { &strike.put };
+    if *open_symbol == position.symbol {
+        return Err(RollError::SameContract);
+    }
+
+    Ok(RollPlan {
+        close_symbol: position.symbol.clone(),
+        close_action,
+        open_symbol: open_symbol.clone(),
+        open_action,
+        quantity: position.quantity.abs(),
+        expiration_date: expiration.expiration_date,
+        strike_price: strike.strike_price,
+    })
+}
+
+/// Plan a roll and build its order in one step. See [`RollPlan::order`] for pricing.
+pub fn roll_position(
+    position: &FullPosition,
+    chain: &NestedOptionChain,
+    target: &RollTarget,
+    close_price: Decimal,
+    open_price: Decimal,
+) -> Result<Order, RollError> {
+    Ok(plan_roll(position, chain, target)?.order(close_price, open_price))
+}
+
+fn select_expiration<'c>(
+    chain: &'c NestedOptionChain,
+    current: &'c Expiration,
+    target: &RollExpiration,
+) -> Result<&'c Expiration, RollError> {
+    let found = match target {
+        RollExpiration::Same => Some(current),
+        RollExpiration::Next => chain
+            .sorted_expirations()
+            .into_iter()
+            .find(|e| e.expiration_date > current.expiration_date),
+        RollExpiration::MinDays(days) => chain.nearest_expiration(*days),
+        RollExpiration::Date(date) => chain.expiration_on(*date),
+    };
+    found.ok_or(RollError::NoExpiration)
+}
+
+fn select_strike<'e>(
+    expiration: &'e Expiration,
+    current: &Strike,
+    is_call: bool,
+    target: &RollStrike,
+) -> Result<&'e Strike, RollError> {
+    let mut strikes: Vec<&Strike> = expiration.strikes.iter().collect();
+    strikes.sort_by_key(|s| s.strike_price);
+    let no_strike = || RollError::NoStrike(expiration.expiration_date);
+
+    match target {
+        RollStrike::Same => strikes
+            .into_iter()
+            .find(|s| s.strike_price == current.strike_price)
+            .ok_or_else(no_strike),
+        RollStrike::Offset(offset) => {
+            let nearest = strikes
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, s)| (s.strike_price - current.strike_price).abs())
+                .map(|(i, _)| i as i64)
+                .ok_or_else(no_strike)?;
+            usize::try_from(nearest + offset)
+                .ok()
+                .and_then(|i| strikes.get(i).copied())
+                .ok_or_else(no_strike)
+        }
+        RollStrike::Delta { current, deltas } => {
+            let option_type = if is_call {
+                OptionType::Call
+            } else {
+                OptionType::Put
+            };
+            expiration
+                .strike_by_delta(*current, option_type, deltas)
+                .ok_or(RollError::MissingDelta(expiration.expiration_date))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_util::dec;
+    use serde_json::json;
+
+    fn chain() -> NestedOptionChain {
+        let strikes = |date: &str| {
+            ["440", "445", "450", "455"]
+                .iter()
+                .map(|k| {
+                    json!({
+                        "strike-price": format!("{}.00", k),
+                        "call": format!("SPY   {}C00{}000", date, k),
+                        "put": format!("SPY   {}P00{}000", date, k),
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        serde_json::from_value(json!({
+            "underlying-symbol": "SPY",
+            "root-symbol": "SPY",
+            "option-chain-type": "Standard",
+            "shares-per-contract": 100,
+            "expirations": [
+                {
+                    "expiration-type": "Regular",
+                    "expiration-date": "2024-02-16",
+                    "days-to-expiration": 45,
+                    "settlement-type": "PM",
+                    "strikes": strikes("240216")
+                },
+                {
+                    "expiration-type": "Weekly",
+                    "expiration-date": "2024-01-19",
+                    "days-to-expiration": 17,
+                    "settlement-type": "PM",
+                    "strikes": strikes("240119")
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn position(symbol: &str, direction: &str, quantity: &str) -> FullPosition {
+        serde_json::from_value(json!({
+            "account-number": "ACC123",
+            "symbol": symbol,
+            "instrument-type": "Equity Option",
+            "underlying-symbol": "SPY",
+            "quantity": quantity,
+            "quantity-direction": direction,
+            "close-price": "2.00",
+            "average-open-price": "3.00",
+            "average-yearly-market-close-price": "0",
+            "average-daily-market-close-price": "0",
+            "multiplier": 100.0,
+            "cost-effect": "Credit",
+            "is-suppressed": false,
+            "is-frozen": false,
+            "restricted-quantity": "0",
+            "realized-day-gain": "0",
+            "realized-day-gain-effect": "None",
+            "realized-day-gain-date": "2024-01-02",
+            "realized-today": "0",
+            "realized-today-effect": "None",
+            "realized-today-date": "2024-01-02",
+            "created-at": "2024-01-02T10:00:00Z",
+            "updated-at": "2024-01-02T16:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_short_put_roll_to_next_expiration_for_credit() {
+        let short_put = position("SPY   240119P00450000", "Short", "2");
+        let plan = plan_roll(&short_put, &chain(), &RollTarget::next_expiration()).unwrap();
+        assert_eq!(plan.open_symbol.0, "SPY   240216P00450000");
+        assert!(matches!(plan.close_action, Action::BuyToClose));
+        assert!(matches!(plan.open_action, Action::SellToOpen));
+        assert_eq!(plan.quantity, dec("2"));
+
+        let order = plan.order(dec("1.20"), dec("3.05"));
+        assert_eq!(order.price, Some(dec("1.85")));
+        assert!(matches!(order.price_effect, PriceEffect::Credit));
+        assert_eq!(order.legs.len(), 2);
+        assert_eq!(order.legs[0].symbol.0, "SPY   240119P00450000");
+        assert_eq!(order.legs[1].symbol.0, "SPY   240216P00450000");
+    }
+
+    #[test]
+    fn test_long_call_roll_with_strike_offset_is_debit() {
+        let long_call = position("SPY   240119C00445000", "Long", "1");
+        let target = RollTarget {
+            expiration: RollExpiration::MinDays(30),
+            strike: RollStrike::Offset(2),
+        };
+        let order = roll_position(&long_call, &chain(), &target, dec("4.00"), dec("4.50")).unwrap();
+        assert_eq!(order.legs[1].symbol.0, "SPY   240216C00455000");
+        assert!(matches!(order.legs[0].action, Action::SellToClose));
+        assert!(matches!(order.legs[1].action, Action::BuyToOpen));
+        assert_eq!(order.price, Some(dec("0.50")));
+        assert!(matches!(order.price_effect, PriceEffect::Debit));
+
+        let too_far = RollTarget {
+            expiration: RollExpiration::Same,
+            strike: RollStrike::Offset(3),
+        };
+        assert!(matches!(
+            plan_roll(&long_call, &chain(), &too_far),
+            Err(RollError::NoStrike(_))
+        ));
+    }
+
+    #[test]
+    fn test_same_delta_roll() {
+        let short_put = position("SPY   240119P00450000", "Short", "1");
+        let deltas = HashMap::from([
+            (Symbol::from("SPY   240216P00440000"), -0.22),
+            (Symbol::from("SPY   240216P00445000"), -0.29),
+            (Symbol::from("SPY   240216P00450000"), -0.37),
+        ]);
+        let target = RollTarget {
+            expiration: RollExpiration::Date(NaiveDate::from_ymd_opt(2024, 2, 16).unwrap()),
+            strike: RollStrike::Delta {
+                current: -0.30,
+                deltas,
+            },
+        };
+        let plan = plan_roll(&short_put, &chain(), &target).unwrap();
+        assert_eq!(plan.open_symbol.0, "SPY   240216P00445000");
+        assert_eq!(plan.strike_price, dec("445.00"));
+        assert_eq!(
+            plan.expiration_date,
+            NaiveDate::from_ymd_opt(2024, 2, 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_roll_errors() {
+        let chain = chain();
+        let unknown = position("SPY   240119P00999000", "Short", "1");
+        assert!(matches!(
+            plan_roll(&unknown, &chain, &RollTarget::next_expiration()),
+            Err(RollError::ContractNotInChain(_))
+        ));
+
+        let last = position("SPY   240216P00450000", "Short", "1");
+        assert!(matches!(
+            plan_roll(&last, &chain, &RollTarget::next_expiration()),
+            Err(RollError::NoExpiration)
+        ));
+
+        let same = RollTarget {
+            expiration: RollExpiration::Same,
+            strike: RollStrike::Same,
+        };
+        assert!(matches!(
+            plan_roll(&last, &chain, &same),
+            Err(RollError::SameContract)
+        ));
+    }
+}
@@ -0,0 +1,14 @@
+//! Helpers shared by the unit tests.
+
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+pub(crate) fn dec(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+pub(crate) fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
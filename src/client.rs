@@ -14,8 +14,10 @@ use crate::api::base::TastyApiResponse;
 use crate::api::base::TastyError;
 use crate::api::auth::AuthState;
 use crate::api::oauth2::{OAuth2AuthRequest, OAuth2Config, OAuth2Token, OAuth2TokenResponse};
+use crate::api::risk::RiskGuard;
 use tokio::sync::{Mutex, RwLock};
 use chrono::Utc;
+use std::sync::Arc;
 use url::Url;
 
 pub const BASE_URL: &str = "https://api.tastyworks.com";
@@ -27,6 +29,7 @@ pub struct TastyTrade {
     base_url: &'static str,
     pub(crate) demo: bool,
     refresh_lock: Mutex<()>,
+    risk_guard: RwLock<Option<Arc<RiskGuard>>>,
 }
 
 pub trait FromTastyResponse<T: DeserializeOwned> {
@@ -126,6 +129,17 @@ impl TastyTrade {
         }
     }
 
+    /// Install (or remove, with `None`) the risk guard consulted before every
+    /// `place_order` and `replace_order` call made through this client.
+    pub async fn set_risk_guard(&self, guard: Option<Arc<RiskGuard>>) {
+        *self.risk_guard.write().await = guard;
+    }
+
+    /// The currently installed risk guard, if any.
+    pub async fn risk_guard(&self) -> Option<Arc<RiskGuard>> {
+        self.risk_guard.read().await.clone()
+    }
+
     fn create_client(config: OAuth2Config, token: OAuth2Token, demo: bool) -> Result<Self> {
         let mut headers = HeaderMap::new();
         headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
@@ -144,6 +158,7 @@ impl TastyTrade {
             base_url: if demo { BASE_DEMO_URL } else { BASE_URL },
             demo,
             refresh_lock: Mutex::new(()),
+            risk_guard: RwLock::new(None),
         })
     }
 
@@ -314,7 +329,8 @@ impl TastyTrade {
 
         match result {
             TastyApiResponse::Success(s) => Ok(R::from_tasty(s)),
-            TastyApiResponse::Error { error } => {
+            TastyApiResponse::Error { mut error } => {
+                error.status = Some(status.as_u16());
                 tracing::error!(
                     code = ?error.code,
                     message = %error.message,
@@ -344,9 +360,9 @@ impl TastyTrade {
         let auth_header = { self.auth_state.read().await.auth_header() };
         req = req.header(header::AUTHORIZATION, auth_header);
 
-        let result = req
-            .send()
-            .await?
+        let response = req.send().await?;
+        let status = response.status();
+        let result = response
             //.inspect_json::<TastyApiResponse<R>, TastyError>(move |text| {
             //    println!("{text}");
             //})
@@ -355,7 +371,35 @@ impl TastyTrade {
 
         match result {
             TastyApiResponse::Success(s) => Ok(s.data),
-            TastyApiResponse::Error { error } => Err(error.into()),
+            TastyApiResponse::Error { mut error } => {
+                error.status = Some(status.as_u16());
+                Err(error.into())
+            }
+        }
+    }
+
+    pub async fn put<R, P, U>(&self, url: U, payload: P) -> Result<R>
+    where
+        R: DeserializeOwned,
+        P: Serialize,
+        U: AsRef<str>,
+    {
+        self.ensure_valid_token().await?;
+        let url = format!("{}{}", self.base_url, url.as_ref());
+        let mut req = self.client.put(url).body(serde_json::to_string(&payload).unwrap());
+        let auth_header = { self.auth_state.read().await.auth_header() };
+        req = req.header(header::AUTHORIZATION, auth_header);
+
+        let response = req.send().await?;
+        let status = response.status();
+        let result = response.json::<TastyApiResponse<R>>().await?;
+
+        match result {
+            TastyApiResponse::Success(s) => Ok(s.data),
+            TastyApiResponse::Error { mut error } => {
+                error.status = Some(status.as_u16());
+                Err(error.into())
+            }
         }
     }
 
@@ -370,9 +414,9 @@ impl TastyTrade {
         let auth_header = { self.auth_state.read().await.auth_header() };
         req = req.header(header::AUTHORIZATION, auth_header);
 
-        let result = req
-            .send()
-            .await?
+        let response = req.send().await?;
+        let status = response.status();
+        let result = response
             // .inspect_json::<TastyApiResponse<R>, TastyError>(move |text| {
             //     println!("{text}");
             // })
@@ -381,7 +425,10 @@ impl TastyTrade {
 
         match result {
             TastyApiResponse::Success(s) => Ok(s.data),
-            TastyApiResponse::Error { error } => Err(error.into()),
+            TastyApiResponse::Error { mut error } => {
+                error.status = Some(status.as_u16());
+                Err(error.into())
+            }
         }
     }
 }